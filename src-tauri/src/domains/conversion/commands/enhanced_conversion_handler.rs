@@ -4,7 +4,7 @@ use std::collections::HashMap;
 use crate::domains::conversion::services::enhanced_conversion_service::EnhancedConversionService;
 use crate::domains::conversion::services::transformation_engine::TransformationEngine;
 use crate::models::enhanced_conversion_map::{
-    EnhancedConversionMap, FieldDefinition, HeaderConversionResult, 
+    EnhancedConversionMap, FieldDefinition, HeaderConversionResult, HeaderMappingPreview,
     TableColumnDefinition, ValidationResult, ApiExtractionResult
 };
 use serde_json::Value;
@@ -56,6 +56,18 @@ pub async fn convert_headers_enhanced(
     service.convert_headers_with_enhanced_map(&excel_headers, &enhanced_map)
 }
 
+#[tauri::command]
+pub async fn preview_header_mapping(
+    state: State<'_, EnhancedConversionState>,
+    excel_headers: Vec<String>,
+    enhanced_map: EnhancedConversionMap,
+) -> Result<HeaderMappingPreview, String> {
+    let service = state.service.lock()
+        .map_err(|_| "Failed to acquire service lock".to_string())?;
+    
+    service.preview_header_mapping(&excel_headers, &enhanced_map)
+}
+
 #[tauri::command]
 pub async fn apply_field_transformations(
     state: State<'_, EnhancedConversionState>,
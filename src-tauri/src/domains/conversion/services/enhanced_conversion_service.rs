@@ -1,13 +1,24 @@
 use crate::models::enhanced_conversion_map::{
-    EnhancedConversionMap, FieldDefinition, MappingType,
-    HeaderConversionResult, ValidationResult, ValidationError, ErrorSeverity,
-    ApiExtractionResult, ExtractionError, TableColumnDefinition, FieldValidationSummary
+    EnhancedConversionMap, FieldDefinition, MappingType, DataType,
+    HeaderConversionResult, HeaderRowDetection, HeaderRowCandidate,
+    HeaderMappingPreview, HeaderMappingSuggestion, HeaderMatchCandidate,
+    HeaderMappingConflict, AmbiguousHeader, SimilarityMetric,
+    ValidationResult, ValidationError, ErrorSeverity,
+    ApiExtractionResult, ExtractionError, TableColumnDefinition, FieldValidationSummary, BlueprintSource
 };
 use crate::domains::conversion::services::transformation_engine::TransformationEngine;
+use crate::domains::conversion::services::json_path::JsonPath;
+use crate::domains::conversion::services::header_matcher::{self, FieldMatch, HeaderMatcher};
+use crate::domains::conversion::services::similarity::levenshtein_similarity;
+use crate::domains::shared::models::api_contracts::parse_cell_reference;
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::path::Path;
 use serde_json::Value;
 
+/// Candidate fields listed per header in a header mapping preview
+const HEADER_PREVIEW_CANDIDATES: usize = 3;
+
 pub struct EnhancedConversionService {
     transformation_engine: TransformationEngine,
 }
@@ -30,6 +41,8 @@ impl EnhancedConversionService {
         
         let enhanced_map: EnhancedConversionMap = serde_json::from_str(enhanced_map_content)
             .map_err(|e| format!("Failed to parse default enhanced conversion map: {}", e))?;
+        HeaderMatcher::for_map(&enhanced_map)?;
+        Self::compile_transformation_patterns(&enhanced_map)?;
 
         log::info!("Loaded default enhanced conversion map with {} field definitions", 
                    enhanced_map.field_definitions.len());
@@ -48,6 +61,9 @@ impl EnhancedConversionService {
 
         let enhanced_map: EnhancedConversionMap = serde_json::from_str(&content)
             .map_err(|e| format!("Failed to parse enhanced conversion map: {}", e))?;
+        HeaderMatcher::for_map(&enhanced_map)
+            .and_then(|_| Self::compile_transformation_patterns(&enhanced_map))
+            .map_err(|e| format!("Invalid enhanced conversion map {}: {}", file_path, e))?;
 
         log::info!("Loaded enhanced conversion map from {} with {} field definitions", 
                    file_path, enhanced_map.field_definitions.len());
@@ -55,6 +71,12 @@ impl EnhancedConversionService {
         Ok(enhanced_map)
     }
 
+    /// Compile the patterns of every transformation rule, failing with the first invalid one
+    fn compile_transformation_patterns(enhanced_map: &EnhancedConversionMap) -> Result<(), String> {
+        enhanced_map.transformation_rules.values()
+            .try_for_each(TransformationEngine::compile_rule_patterns)
+    }
+
     pub fn save_enhanced_conversion_map(&self, enhanced_map: &EnhancedConversionMap, file_path: &str) -> Result<(), String> {
         let json = serde_json::to_string_pretty(enhanced_map)
             .map_err(|e| format!("Failed to serialize enhanced conversion map: {}", e))?;
@@ -76,17 +98,30 @@ impl EnhancedConversionService {
         let mut validation_errors = Vec::new();
         let mut mapping_confidence = HashMap::new();
 
+        let header_matcher = HeaderMatcher::for_map(enhanced_map)?;
+        let (assignments, conflicts, ambiguous_headers) = self.assign_headers(excel_headers, &header_matcher);
         for excel_header in excel_headers {
-            if let Some((field_name, confidence)) = self.find_best_field_match(excel_header, enhanced_map) {
-                converted_headers.insert(excel_header.clone(), field_name.clone());
-                mapping_confidence.insert(excel_header.clone(), confidence);
+            if converted_headers.contains_key(excel_header) {
+                continue;
+            }
+            if let Some(field_match) = assignments.get(excel_header) {
+                converted_headers.insert(excel_header.clone(), field_match.field_name.clone());
+                mapping_confidence.insert(excel_header.clone(), field_match.confidence);
 
                 // Apply transformations if any
-                if let Some(field_def) = enhanced_map.field_definitions.get(&field_name) {
+                if let Some(field_def) = enhanced_map.field_definitions.get(&field_match.field_name) {
                     if let Some(transformations) = &field_def.transformations {
                         applied_transformations.insert(excel_header.clone(), transformations.join(", "));
                     }
                 }
+            } else if let Some(conflict) = conflicts.iter().find(|conflict| &conflict.rejected_header == excel_header) {
+                validation_errors.push(ValidationError {
+                    field: excel_header.clone(),
+                    message: format!("Header '{}' also matches field {}, which is already mapped from '{}'{}",
+                                     excel_header, conflict.field_name, conflict.assigned_header,
+                                     if conflict.tie { " (tied, the leftmost header wins)" } else { "" }),
+                    severity: ErrorSeverity::Warning,
+                });
             } else {
                 validation_errors.push(ValidationError {
                     field: excel_header.clone(),
@@ -95,117 +130,250 @@ impl EnhancedConversionService {
                 });
             }
         }
+        for ambiguous in &ambiguous_headers {
+            validation_errors.push(ValidationError {
+                field: ambiguous.header.clone(),
+                message: format!("Header '{}' matches {} and {} equally; mapped to {}",
+                                 ambiguous.header, ambiguous.assigned_field, ambiguous.tied_fields.join(", "), ambiguous.assigned_field),
+                severity: ErrorSeverity::Warning,
+            });
+        }
 
         Ok(HeaderConversionResult {
             converted_headers,
             applied_transformations,
             validation_errors,
             mapping_confidence,
+            conflicts,
+            ambiguous_headers,
         })
     }
 
-    /// Normalize whitespace characters (\r\n, \n, \t, etc.) to single spaces for header matching
-    fn normalize_whitespace(text: &str) -> String {
-        use regex::Regex;
-        lazy_static::lazy_static! {
-            static ref WHITESPACE_REGEX: Regex = Regex::new(r"\s+").unwrap();
+    /// Assign headers to fields one-to-one across the whole header row
+    /// 
+    /// All header/field matches are ranked by confidence, then by mapping priority, and taken
+    /// best first; a header or field that is already assigned is skipped. Equal ranks are
+    /// settled by column order and field name, and reported as ties rather than left to chance.
+    fn assign_headers(
+        &self,
+        excel_headers: &[String],
+        header_matcher: &HeaderMatcher
+    ) -> (HashMap<String, FieldMatch>, Vec<HeaderMappingConflict>, Vec<AmbiguousHeader>) {
+        let mut unique_headers: Vec<&String> = Vec::new();
+        for excel_header in excel_headers {
+            if !unique_headers.contains(&excel_header) {
+                unique_headers.push(excel_header);
+            }
         }
-        WHITESPACE_REGEX.replace_all(text.trim(), " ").to_string()
-    }
-
-    fn find_best_field_match(&self, excel_header: &str, enhanced_map: &EnhancedConversionMap) -> Option<(String, f64)> {
-        let mut best_match = None;
-        let mut best_confidence = 0.0;
 
-        for (field_name, field_def) in &enhanced_map.field_definitions {
-            for xlsx_mapping in &field_def.xlsx_mappings {
-                let confidence = match &xlsx_mapping.mapping_type {
-                    MappingType::Exact => {
-                        // Apply whitespace normalization to both pattern and header
-                        let normalized_pattern = Self::normalize_whitespace(&xlsx_mapping.pattern);
-                        let normalized_header = Self::normalize_whitespace(excel_header);
-                        
-                        let pattern = if xlsx_mapping.case_sensitive {
-                            &normalized_pattern
-                        } else {
-                            &normalized_pattern.to_lowercase()
-                        };
-                        let header = if xlsx_mapping.case_sensitive {
-                            &normalized_header
-                        } else {
-                            &normalized_header.to_lowercase()
-                        };
-                        if pattern == header { 1.0 } else { 0.0 }
-                    },
-                    MappingType::Partial => {
-                        // Apply whitespace normalization to both pattern and header
-                        let normalized_pattern = Self::normalize_whitespace(&xlsx_mapping.pattern);
-                        let normalized_header = Self::normalize_whitespace(excel_header);
-                        
-                        let pattern = if xlsx_mapping.case_sensitive {
-                            &normalized_pattern
-                        } else {
-                            &normalized_pattern.to_lowercase()
-                        };
-                        let header = if xlsx_mapping.case_sensitive {
-                            &normalized_header
-                        } else {
-                            &normalized_header.to_lowercase()
-                        };
-                        if header.contains(pattern) { 0.8 } else { 0.0 }
-                    },
-                    MappingType::Regex => {
-                        if let Ok(regex) = regex::Regex::new(&xlsx_mapping.pattern) {
-                            if regex.is_match(excel_header) { 0.9 } else { 0.0 }
-                        } else {
-                            0.0
-                        }
-                    },
-                    MappingType::Fuzzy => {
-                        self.calculate_fuzzy_confidence(&xlsx_mapping.pattern, excel_header)
-                    }
-                };
-
-                if confidence > best_confidence {
-                    best_confidence = confidence;
-                    best_match = Some(field_name.clone());
+        let mut matches: Vec<(usize, FieldMatch)> = unique_headers.iter()
+            .enumerate()
+            .flat_map(|(header_idx, excel_header)| {
+                header_matcher.field_matches(excel_header).into_iter()
+                    .map(move |field_match| (header_idx, field_match))
+            })
+            .collect();
+        matches.sort_by(|(a_idx, a), (b_idx, b)| b.rank(a)
+            .then(a_idx.cmp(b_idx))
+            .then_with(|| a.field_name.cmp(&b.field_name)));
+
+        let mut assignments: HashMap<String, FieldMatch> = HashMap::new();
+        let mut field_owners: HashMap<String, (usize, FieldMatch)> = HashMap::new();
+        let mut conflicts = Vec::new();
+        let mut tied_fields: HashMap<usize, Vec<String>> = HashMap::new();
+
+        for (header_idx, field_match) in matches {
+            let excel_header = unique_headers[header_idx];
+            if let Some(assigned) = assignments.get(excel_header) {
+                if assigned.rank(&field_match) == Ordering::Equal && !field_owners.contains_key(&field_match.field_name) {
+                    tied_fields.entry(header_idx).or_default().push(field_match.field_name);
                 }
+                continue;
             }
+            if let Some((owner_idx, owner_match)) = field_owners.get(&field_match.field_name) {
+                conflicts.push(HeaderMappingConflict {
+                    field_name: field_match.field_name.clone(),
+                    assigned_header: unique_headers[*owner_idx].clone(),
+                    rejected_header: excel_header.clone(),
+                    tie: owner_match.rank(&field_match) == Ordering::Equal,
+                });
+                continue;
+            }
+            field_owners.insert(field_match.field_name.clone(), (header_idx, field_match.clone()));
+            assignments.insert(excel_header.clone(), field_match);
+        }
+
+        // A rejected header that still found another field is not left without a mapping
+        conflicts.retain(|conflict| !assignments.contains_key(&conflict.rejected_header));
+        for conflict in &conflicts {
+            log::warn!("Header '{}' not mapped: field {} already mapped from '{}'{}",
+                       conflict.rejected_header, conflict.field_name, conflict.assigned_header,
+                       if conflict.tie { " (tie)" } else { "" });
         }
 
-        best_match.map(|field| (field, best_confidence))
+        let mut tied: Vec<(usize, Vec<String>)> = tied_fields.into_iter().collect();
+        tied.sort_by_key(|(header_idx, _)| *header_idx);
+        let ambiguous_headers = tied.into_iter()
+            .map(|(header_idx, tied_fields)| AmbiguousHeader {
+                header: unique_headers[header_idx].clone(),
+                assigned_field: assignments[unique_headers[header_idx]].field_name.clone(),
+                tied_fields,
+            })
+            .collect();
+
+        (assignments, conflicts, ambiguous_headers)
     }
 
-    fn calculate_fuzzy_confidence(&self, pattern: &str, text: &str) -> f64 {
-        let max_len = pattern.len().max(text.len());
-        if max_len == 0 { return 1.0; }
+    /// Rank the candidate fields of every header, and list the fields no header maps to
+    /// 
+    /// Candidates come from the xlsx mappings; a header none of them match gets candidates
+    /// ranked by fuzzy similarity to field names, display names and mapping patterns instead.
+    /// `mapped_field` is the field that header conversion would pick.
+    pub fn preview_header_mapping(&self, excel_headers: &[String], enhanced_map: &EnhancedConversionMap) -> Result<HeaderMappingPreview, String> {
+        let header_matcher = HeaderMatcher::for_map(enhanced_map)?;
+        let (assignments, _, _) = self.assign_headers(excel_headers, &header_matcher);
+        let mut headers = Vec::new();
+        for excel_header in excel_headers {
+            let mut candidates = self.mapping_candidates(excel_header, &header_matcher);
+            if candidates.is_empty() {
+                candidates = self.similarity_candidates(excel_header, enhanced_map);
+            }
+            candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence).then_with(|| a.field_name.cmp(&b.field_name)));
+            candidates.truncate(HEADER_PREVIEW_CANDIDATES);
+            
+            headers.push(HeaderMappingSuggestion {
+                header: excel_header.clone(),
+                mapped_field: assignments.get(excel_header).map(|field_match| field_match.field_name.clone()),
+                candidates,
+            });
+        }
+        
+        let mut unmatched_fields: Vec<String> = enhanced_map.field_definitions.keys()
+            .filter(|field_name| !headers.iter().any(|suggestion| suggestion.mapped_field.as_ref() == Some(*field_name)))
+            .cloned()
+            .collect();
+        unmatched_fields.sort();
+        let missing_required_fields = unmatched_fields.iter()
+            .filter(|field_name| enhanced_map.field_definitions[*field_name].is_required)
+            .cloned()
+            .collect();
         
-        let distance = self.levenshtein_distance(pattern, text);
-        1.0 - (distance as f64 / max_len as f64)
+        Ok(HeaderMappingPreview {
+            headers,
+            unmatched_fields,
+            missing_required_fields,
+        })
     }
 
-    fn levenshtein_distance(&self, s1: &str, s2: &str) -> usize {
-        let len1 = s1.len();
-        let len2 = s2.len();
-        let mut matrix = vec![vec![0; len2 + 1]; len1 + 1];
+    /// Best xlsx mapping confidence of each field that matches the header
+    fn mapping_candidates(&self, excel_header: &str, header_matcher: &HeaderMatcher) -> Vec<HeaderMatchCandidate> {
+        header_matcher.field_matches(excel_header).into_iter()
+            .map(|field_match| HeaderMatchCandidate {
+                field_name: field_match.field_name,
+                confidence: field_match.confidence,
+                matched_pattern: Some(field_match.pattern),
+            })
+            .collect()
+    }
 
-        for (i, row) in matrix.iter_mut().enumerate().take(len1 + 1) {
-            row[0] = i;
-        }
-        for j in 0..=len2 {
-            matrix[0][j] = j;
-        }
+    /// Fuzzy similarity of the header to each field's name, display name and mapping patterns
+    fn similarity_candidates(&self, excel_header: &str, enhanced_map: &EnhancedConversionMap) -> Vec<HeaderMatchCandidate> {
+        let header = header_matcher::normalize_whitespace(excel_header).to_lowercase();
+        enhanced_map.field_definitions.iter()
+            .filter_map(|(field_name, field_def)| {
+                let confidence = [field_name.replace('_', " "), field_def.display_name.clone()].into_iter()
+                    .chain(field_def.xlsx_mappings.iter().map(|xlsx_mapping| xlsx_mapping.pattern.clone()))
+                    .map(|name| levenshtein_similarity(&header_matcher::normalize_whitespace(&name).to_lowercase(), &header))
+                    .fold(0.0, f64::max);
+                (confidence > 0.0).then(|| HeaderMatchCandidate {
+                    field_name: field_name.clone(),
+                    confidence,
+                    matched_pattern: None,
+                })
+            })
+            .collect()
+    }
 
-        for i in 1..=len1 {
-            for j in 1..=len2 {
-                let cost = if s1.chars().nth(i - 1) == s2.chars().nth(j - 1) { 0 } else { 1 };
-                matrix[i][j] = (matrix[i - 1][j] + 1)
-                    .min(matrix[i][j - 1] + 1)
-                    .min(matrix[i - 1][j - 1] + cost);
+    /// Detect the header row by scoring candidate rows against all field xlsx mappings
+    /// 
+    /// Each row's score is the sum of the best match confidence of every distinct field
+    /// matched by one of its cells. The highest scoring row wins, earlier rows win ties.
+    /// The reported confidence is the mean confidence of the matched fields scaled by the
+    /// fraction of required fields that were found. Returns None when no row matches any field.
+    /// Rows are numbered from 1 in candidate order, so candidates taken from the top of a sheet
+    /// are numbered by sheet row.
+    pub fn detect_header_row(
+        &self,
+        candidate_rows: &[Vec<String>],
+        enhanced_map: &EnhancedConversionMap,
+    ) -> Result<Option<HeaderRowDetection>, String> {
+        let header_matcher = HeaderMatcher::for_map(enhanced_map)?;
+        let required_fields: Vec<&String> = enhanced_map.field_definitions.iter()
+            .filter(|(_, field_def)| field_def.is_required)
+            .map(|(field_name, _)| field_name)
+            .collect();
+
+        let mut best: Option<(usize, HashMap<String, f64>)> = None;
+        let mut candidates = Vec::new();
+
+        for (row_idx, row) in candidate_rows.iter().enumerate() {
+            let mut field_confidence: HashMap<String, f64> = HashMap::new();
+            for cell in row.iter().filter(|cell| !cell.trim().is_empty()) {
+                if let Some(field_match) = header_matcher.best_match(cell) {
+                    let entry = field_confidence.entry(field_match.field_name).or_insert(0.0);
+                    *entry = entry.max(field_match.confidence);
+                }
+            }
+
+            let score: f64 = field_confidence.values().sum();
+            candidates.push(HeaderRowCandidate {
+                header_row: row_idx as u32 + 1,
+                score,
+                confidence: Self::header_row_confidence(&field_confidence, &required_fields),
+                matched_field_count: field_confidence.len(),
+            });
+
+            let best_score = best.as_ref()
+                .map(|(_, fields)| fields.values().sum::<f64>())
+                .unwrap_or(0.0);
+            if score > best_score {
+                best = Some((row_idx, field_confidence));
             }
         }
 
-        matrix[len1][len2]
+        let Some((row_idx, field_confidence)) = best else {
+            return Ok(None);
+        };
+        let mut matched_fields: Vec<String> = field_confidence.keys().cloned().collect();
+        matched_fields.sort();
+        let mut missing_required_fields: Vec<String> = required_fields.iter()
+            .filter(|field_name| !field_confidence.contains_key(field_name.as_str()))
+            .map(|field_name| field_name.to_string())
+            .collect();
+        missing_required_fields.sort();
+
+        Ok(Some(HeaderRowDetection {
+            header_row: row_idx as u32 + 1,
+            confidence: Self::header_row_confidence(&field_confidence, &required_fields),
+            matched_fields,
+            missing_required_fields,
+            candidates,
+        }))
+    }
+
+    fn header_row_confidence(field_confidence: &HashMap<String, f64>, required_fields: &[&String]) -> f64 {
+        if field_confidence.is_empty() {
+            return 0.0;
+        }
+        let mean_confidence = field_confidence.values().sum::<f64>() / field_confidence.len() as f64;
+        let required_coverage = if required_fields.is_empty() {
+            1.0
+        } else {
+            required_fields.iter()
+                .filter(|field_name| field_confidence.contains_key(field_name.as_str()))
+                .count() as f64 / required_fields.len() as f64
+        };
+        mean_confidence * required_coverage
     }
 
     pub fn apply_field_transformations(
@@ -213,7 +381,21 @@ impl EnhancedConversionService {
         field_data: &HashMap<String, String>,
         enhanced_map: &EnhancedConversionMap,
     ) -> Result<HashMap<String, String>, String> {
+        self.apply_field_transformations_with_failures(field_data, enhanced_map)
+            .map(|(transformed_data, _)| transformed_data)
+    }
+
+    /// Apply field transformations and also return a description of every transformation that failed
+    /// 
+    /// A field whose transformation fails keeps the value it had before that transformation.
+    /// The value of an `array` field is then normalized by its `array_format`.
+    pub fn apply_field_transformations_with_failures(
+        &self,
+        field_data: &HashMap<String, String>,
+        enhanced_map: &EnhancedConversionMap,
+    ) -> Result<(HashMap<String, String>, Vec<String>), String> {
         let mut transformed_data = HashMap::new();
+        let mut failures = Vec::new();
 
         for (field_name, value) in field_data {
             let mut transformed_value = value.clone();
@@ -229,19 +411,25 @@ impl EnhancedConversionService {
                                     log::debug!("Transformation '{}' result: '{}' -> '{}'", transformation_name, transformed_value, new_value);
                                     transformed_value = new_value;
                                 },
-                                Err(e) => log::warn!("Transformation {} failed for field {}: {}", transformation_name, field_name, e),
+                                Err(e) => {
+                                    log::warn!("Transformation {} failed for field {}: {}", transformation_name, field_name, e);
+                                    failures.push(format!("Transformation '{}' failed for field '{}': {}", transformation_name, field_name, e));
+                                }
                             }
                         } else {
                             log::warn!("Transformation rule '{}' not found for field '{}'", transformation_name, field_name);
                         }
                     }
                 }
+                if matches!(field_def.data_type, DataType::Array) {
+                    transformed_value = field_def.array_format.normalize(&transformed_value);
+                }
             }
 
             transformed_data.insert(field_name.clone(), transformed_value);
         }
 
-        Ok(transformed_data)
+        Ok((transformed_data, failures))
     }
 
     pub fn validate_field_values(
@@ -343,35 +531,36 @@ impl EnhancedConversionService {
 
         for (field_name, field_def) in &enhanced_map.field_definitions {
             let mut extracted = false;
-
-            for api_mapping in &field_def.api_mappings {
-                // Try primary path first
-                if let Some(value) = self.extract_json_path(&api_mapping.primary_path, api_response) {
+            let mut invalid_paths = Vec::new();
+
+            // Try each mapping's primary path, then its fallback paths
+            let paths = field_def.api_mappings.iter()
+                .flat_map(|api_mapping| std::iter::once(&api_mapping.primary_path).chain(&api_mapping.fallback_paths));
+            for path in paths {
+                let json_path = match JsonPath::parse(path) {
+                    Ok(json_path) => json_path,
+                    Err(e) => {
+                        invalid_paths.push(e);
+                        continue;
+                    }
+                };
+                if let Some(value) = json_path.extract(api_response, &field_def.data_type) {
                     extracted_data.insert(field_name.clone(), value);
                     success_count += 1;
                     extracted = true;
                     break;
                 }
-
-                // Try fallback paths
-                for fallback_path in &api_mapping.fallback_paths {
-                    if let Some(value) = self.extract_json_path(fallback_path, api_response) {
-                        extracted_data.insert(field_name.clone(), value);
-                        success_count += 1;
-                        extracted = true;
-                        break;
-                    }
-                }
-
-                if extracted {
-                    break;
-                }
             }
 
             if !extracted && !field_def.api_mappings.is_empty() {
+                let message = if invalid_paths.is_empty() {
+                    "Failed to extract value from API response".to_string()
+                } else {
+                    format!("Failed to extract value from API response: {}", invalid_paths.join("; "))
+                };
                 extraction_errors.push(ExtractionError {
                     field: field_name.clone(),
-                    message: "Failed to extract value from API response".to_string(),
+                    message,
                     path: field_def.api_mappings.first()
                         .map(|m| m.primary_path.clone())
                         .unwrap_or_default(),
@@ -387,49 +576,6 @@ impl EnhancedConversionService {
         })
     }
 
-    fn extract_json_path(&self, path: &str, data: &Value) -> Option<Value> {
-        // Simple JSONPath-like extraction
-        // For now, just extract simple dot-notation paths
-        let parts: Vec<&str> = path.split('.').collect();
-        let mut current = data;
-
-        for part in parts {
-            if part.is_empty() { continue; }
-            
-            if part.starts_with('$') {
-                continue; // Skip root indicator
-            }
-
-            if let Some(clean_part) = part.strip_suffix("?") {
-                // Optional path, return None if not found
-                if let Some(value) = current.get(clean_part) {
-                    current = value;
-                } else {
-                    return None;
-                }
-            } else if let Some(clean_part) = part.strip_suffix("[*]") {
-                // Array extraction
-                if let Some(array) = current.get(clean_part).and_then(|v| v.as_array()) {
-                    // Return first non-null value for simplicity
-                    for item in array {
-                        if !item.is_null() {
-                            current = item;
-                            break;
-                        }
-                    }
-                } else {
-                    return None;
-                }
-            } else if let Some(value) = current.get(part) {
-                current = value;
-            } else {
-                return None;
-            }
-        }
-
-        Some(current.clone())
-    }
-
     pub fn generate_table_columns(
         &self,
         enhanced_map: &EnhancedConversionMap,
@@ -487,8 +633,12 @@ impl EnhancedConversionService {
         let mut enhanced_map = EnhancedConversionMap {
             version: "1.0.0".to_string(),
             header_row,
+            header_row_span: None,
+            header_detection: None,
             field_definitions: HashMap::new(),
             transformation_rules: HashMap::new(),
+            blueprint_sources: Vec::new(),
+            port_range_expansion: None,
             created_at: Some(chrono::Utc::now().to_rfc3339()),
             updated_at: Some(chrono::Utc::now().to_rfc3339()),
         };
@@ -525,6 +675,19 @@ impl EnhancedConversionService {
             }
         }
 
+        // Validate regex xlsx mappings
+        for (field_name, field_def) in &enhanced_map.field_definitions {
+            for xlsx_mapping in field_def.xlsx_mappings.iter().filter(|xlsx_mapping| xlsx_mapping.mapping_type == MappingType::Regex) {
+                if let Err(e) = header_matcher::compile_regex(xlsx_mapping) {
+                    errors.push(ValidationError {
+                        field: field_name.clone(),
+                        message: format!("Invalid xlsx mapping regex '{}': {}", xlsx_mapping.pattern, e),
+                        severity: ErrorSeverity::Error,
+                    });
+                }
+            }
+        }
+
         // Validate transformation rules
         for (rule_name, rule) in &enhanced_map.transformation_rules {
             if let Err(e) = self.transformation_engine.validate_transformation_rule(rule) {
@@ -536,6 +699,47 @@ impl EnhancedConversionService {
             }
         }
 
+        // Validate blueprint sources
+        for source in &enhanced_map.blueprint_sources {
+            match source {
+                BlueprintSource::Cell { cell } if parse_cell_reference(cell).is_none() => {
+                    errors.push(ValidationError {
+                        field: "blueprint".to_string(),
+                        message: format!("Invalid blueprint cell reference '{}'", cell),
+                        severity: ErrorSeverity::Error,
+                    });
+                }
+                BlueprintSource::SheetName { pattern } => {
+                    if let Err(e) = regex::Regex::new(pattern) {
+                        errors.push(ValidationError {
+                            field: "blueprint".to_string(),
+                            message: format!("Invalid blueprint sheet name pattern '{}': {}", pattern, e),
+                            severity: ErrorSeverity::Error,
+                        });
+                    }
+                }
+                BlueprintSource::Column if !enhanced_map.field_definitions.contains_key("blueprint") => {
+                    warnings.push(ValidationError {
+                        field: "blueprint".to_string(),
+                        message: "Blueprint column source is configured but no blueprint field is defined".to_string(),
+                        severity: ErrorSeverity::Warning,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        // Validate port range expansion fields
+        if let Some(expansion) = &enhanced_map.port_range_expansion {
+            for field_name in expansion.fields.iter().filter(|field_name| !enhanced_map.field_definitions.contains_key(*field_name)) {
+                warnings.push(ValidationError {
+                    field: field_name.clone(),
+                    message: format!("Port range expansion field '{}' is not defined", field_name),
+                    severity: ErrorSeverity::Warning,
+                });
+            }
+        }
+
         let mut field_summary = HashMap::new();
         for field_name in enhanced_map.field_definitions.keys() {
             field_summary.insert(field_name.clone(), FieldValidationSummary {
@@ -566,6 +770,8 @@ impl EnhancedConversionService {
                 priority: 100,
                 case_sensitive: false,
                 transform: None,
+                similarity: SimilarityMetric::default(),
+                min_confidence: None,
             }],
             api_mappings: vec![],
             validation_rules: crate::models::enhanced_conversion_map::ValidationRules {
@@ -583,6 +789,9 @@ impl EnhancedConversionService {
                 hidden: false,
             }),
             transformations: None,
+            merge_behavior: crate::models::enhanced_conversion_map::MergeBehavior::default(),
+            cell_coercion: crate::models::enhanced_conversion_map::CellCoercion::default(),
+            array_format: crate::models::enhanced_conversion_map::ArrayFormat::default(),
         })
     }
 }
\ No newline at end of file
@@ -0,0 +1,311 @@
+//! Precompiled header matching for the xlsx mappings of an enhanced conversion map
+//!
+//! Patterns are normalized and regexes compiled once per map, so matching a header row no longer
+//! recompiles every regex for every header. Matchers are cached by a hash of the map's xlsx mappings.
+
+use crate::domains::conversion::services::similarity::{levenshtein_similarity, jaro_winkler, token_set_ratio};
+use crate::models::enhanced_conversion_map::{EnhancedConversionMap, MappingType, SimilarityMetric, XlsxMapping};
+use regex::{Regex, RegexBuilder};
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+use std::mem;
+use std::sync::{Arc, Mutex};
+
+/// Lowest similarity a fuzzy mapping accepts when it sets no `min_confidence`
+const DEFAULT_FUZZY_MIN_CONFIDENCE: f64 = 0.6;
+
+/// Highest confidence of a fuzzy mapping, kept below an exact match so exact matches always win
+const MAX_FUZZY_CONFIDENCE: f64 = 0.99;
+
+/// Compiled matchers kept before the least recently used one is evicted
+const MATCHER_CACHE_CAPACITY: usize = 16;
+
+lazy_static::lazy_static! {
+    /// Matchers by mappings hash, least recently used first
+    static ref MATCHER_CACHE: Mutex<VecDeque<(u64, Arc<HeaderMatcher>)>> = Mutex::new(VecDeque::new());
+}
+
+/// The best matching xlsx mapping of one field for a header
+#[derive(Debug, Clone)]
+pub struct FieldMatch {
+    pub field_name: String,
+    pub confidence: f64,
+    pub priority: u32,
+    pub pattern: String,
+}
+
+impl FieldMatch {
+    /// Higher confidence ranks first so exact matches always win; priority breaks equal confidence
+    pub fn rank(&self, other: &Self) -> Ordering {
+        self.confidence.total_cmp(&other.confidence)
+            .then(self.priority.cmp(&other.priority))
+    }
+}
+
+enum CompiledPattern {
+    Exact(String),
+    Partial(String),
+    Regex(Regex),
+    Fuzzy(String, SimilarityMetric),
+}
+
+struct CompiledMapping {
+    pattern: String,
+    priority: u32,
+    case_sensitive: bool,
+    min_confidence: f64,
+    compiled: CompiledPattern,
+}
+
+/// The xlsx mappings of every field, compiled for matching headers
+pub struct HeaderMatcher {
+    fields: Vec<(String, Vec<CompiledMapping>)>,
+}
+
+impl HeaderMatcher {
+    /// Compile the xlsx mappings of a map, failing with every invalid regex pattern
+    pub fn new(enhanced_map: &EnhancedConversionMap) -> Result<Self, String> {
+        let mut field_names: Vec<&String> = enhanced_map.field_definitions.keys().collect();
+        field_names.sort();
+
+        let mut fields = Vec::new();
+        let mut errors = Vec::new();
+        for field_name in field_names {
+            let mut mappings = Vec::new();
+            for xlsx_mapping in &enhanced_map.field_definitions[field_name].xlsx_mappings {
+                match CompiledMapping::new(xlsx_mapping) {
+                    Ok(mapping) => mappings.push(mapping),
+                    Err(e) => errors.push(format!("field {}: {}", field_name, e)),
+                }
+            }
+            fields.push((field_name.clone(), mappings));
+        }
+
+        if !errors.is_empty() {
+            return Err(format!("Invalid xlsx mapping pattern: {}", errors.join("; ")));
+        }
+        Ok(Self { fields })
+    }
+
+    /// Matcher of a map, compiled on first use and reused while its xlsx mappings are unchanged
+    pub fn for_map(enhanced_map: &EnhancedConversionMap) -> Result<Arc<Self>, String> {
+        let key = mappings_hash(enhanced_map);
+
+        let mut cache = MATCHER_CACHE.lock()
+            .map_err(|_| "Failed to acquire header matcher cache lock".to_string())?;
+        if let Some(position) = cache.iter().position(|(cached_key, _)| *cached_key == key) {
+            let entry = cache.remove(position).unwrap();
+            let matcher = Arc::clone(&entry.1);
+            cache.push_back(entry);
+            return Ok(matcher);
+        }
+        let matcher = Arc::new(Self::new(enhanced_map)?);
+        if cache.len() >= MATCHER_CACHE_CAPACITY {
+            cache.pop_front();
+        }
+        cache.push_back((key, Arc::clone(&matcher)));
+        Ok(matcher)
+    }
+
+    /// Every field with an xlsx mapping that matches the header, with its best ranked mapping
+    pub fn field_matches(&self, excel_header: &str) -> Vec<FieldMatch> {
+        let normalized_header = normalize_whitespace(excel_header);
+        let lowercase_header = normalized_header.to_lowercase();
+        self.fields.iter()
+            .filter_map(|(field_name, mappings)| {
+                mappings.iter()
+                    .map(|mapping| {
+                        let header = if mapping.case_sensitive { &normalized_header } else { &lowercase_header };
+                        FieldMatch {
+                            field_name: field_name.clone(),
+                            confidence: mapping.confidence(excel_header, header),
+                            priority: mapping.priority,
+                            pattern: mapping.pattern.clone(),
+                        }
+                    })
+                    .filter(|field_match| field_match.confidence > 0.0)
+                    .max_by(|a, b| a.rank(b))
+            })
+            .collect()
+    }
+
+    /// Best matching field of a single header, ranked like header assignment
+    pub fn best_match(&self, excel_header: &str) -> Option<FieldMatch> {
+        self.field_matches(excel_header).into_iter()
+            .max_by(|a, b| a.rank(b).then_with(|| b.field_name.cmp(&a.field_name)))
+    }
+}
+
+/// Hash of every field's xlsx mappings, covering only what the compiled matcher depends on
+fn mappings_hash(enhanced_map: &EnhancedConversionMap) -> u64 {
+    let mut field_names: Vec<&String> = enhanced_map.field_definitions.keys().collect();
+    field_names.sort();
+
+    let mut hasher = DefaultHasher::new();
+    for field_name in field_names {
+        field_name.hash(&mut hasher);
+        let xlsx_mappings = &enhanced_map.field_definitions[field_name].xlsx_mappings;
+        xlsx_mappings.len().hash(&mut hasher);
+        for xlsx_mapping in xlsx_mappings {
+            hash_mapping(xlsx_mapping, &mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+fn hash_mapping(xlsx_mapping: &XlsxMapping, hasher: &mut DefaultHasher) {
+    xlsx_mapping.pattern.hash(hasher);
+    mem::discriminant(&xlsx_mapping.mapping_type).hash(hasher);
+    xlsx_mapping.priority.hash(hasher);
+    xlsx_mapping.case_sensitive.hash(hasher);
+    mem::discriminant(&xlsx_mapping.similarity).hash(hasher);
+    xlsx_mapping.min_confidence.map(f64::to_bits).hash(hasher);
+}
+
+impl CompiledMapping {
+    fn new(xlsx_mapping: &XlsxMapping) -> Result<Self, String> {
+        let text = if xlsx_mapping.case_sensitive {
+            normalize_whitespace(&xlsx_mapping.pattern)
+        } else {
+            normalize_whitespace(&xlsx_mapping.pattern).to_lowercase()
+        };
+        let compiled = match xlsx_mapping.mapping_type {
+            MappingType::Exact => CompiledPattern::Exact(text),
+            MappingType::Partial => CompiledPattern::Partial(text),
+            MappingType::Regex => CompiledPattern::Regex(compile_regex(xlsx_mapping)
+                .map_err(|e| format!("regex '{}' does not compile: {}", xlsx_mapping.pattern, e))?),
+            MappingType::Fuzzy => CompiledPattern::Fuzzy(text, xlsx_mapping.similarity),
+        };
+        let min_confidence = match (xlsx_mapping.min_confidence, &xlsx_mapping.mapping_type) {
+            (Some(min_confidence), _) => min_confidence,
+            (None, MappingType::Fuzzy) => DEFAULT_FUZZY_MIN_CONFIDENCE,
+            (None, _) => 0.0,
+        };
+
+        Ok(Self {
+            pattern: xlsx_mapping.pattern.clone(),
+            priority: xlsx_mapping.priority,
+            case_sensitive: xlsx_mapping.case_sensitive,
+            min_confidence,
+            compiled,
+        })
+    }
+
+    /// Confidence of the match (0.0 when it does not match or is below `min_confidence`)
+    ///
+    /// Regexes see the raw header; the other patterns see the normalized, case-folded header.
+    fn confidence(&self, raw_header: &str, header: &str) -> f64 {
+        let confidence = match &self.compiled {
+            CompiledPattern::Exact(text) => if header == text { 1.0 } else { 0.0 },
+            CompiledPattern::Partial(text) => if header.contains(text.as_str()) { 0.8 } else { 0.0 },
+            CompiledPattern::Regex(regex) => if regex.is_match(raw_header) { 0.9 } else { 0.0 },
+            CompiledPattern::Fuzzy(text, similarity) => match similarity {
+                SimilarityMetric::Levenshtein => levenshtein_similarity(text, header),
+                SimilarityMetric::JaroWinkler => jaro_winkler(text, header),
+                SimilarityMetric::TokenSet => token_set_ratio(text, header),
+            }.min(MAX_FUZZY_CONFIDENCE),
+        };
+        if confidence >= self.min_confidence { confidence } else { 0.0 }
+    }
+}
+
+/// Compile the pattern of a regex mapping, case-insensitive unless the mapping is case sensitive
+pub fn compile_regex(xlsx_mapping: &XlsxMapping) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(&xlsx_mapping.pattern)
+        .case_insensitive(!xlsx_mapping.case_sensitive)
+        .build()
+}
+
+/// Normalize whitespace characters (\r\n, \n, \t, etc.) to single spaces for header matching
+pub fn normalize_whitespace(text: &str) -> String {
+    lazy_static::lazy_static! {
+        static ref WHITESPACE_REGEX: Regex = Regex::new(r"\s+").unwrap();
+    }
+    WHITESPACE_REGEX.replace_all(text.trim(), " ").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::conversion::services::enhanced_conversion_service::EnhancedConversionService;
+
+    fn regex_mapping(pattern: &str, case_sensitive: bool) -> XlsxMapping {
+        XlsxMapping {
+            pattern: pattern.to_string(),
+            mapping_type: MappingType::Regex,
+            priority: 80,
+            case_sensitive,
+            transform: None,
+            similarity: SimilarityMetric::default(),
+            min_confidence: None,
+        }
+    }
+
+    fn fuzzy_mapping(pattern: &str, similarity: SimilarityMetric, priority: u32) -> XlsxMapping {
+        XlsxMapping {
+            mapping_type: MappingType::Fuzzy,
+            priority,
+            similarity,
+            ..regex_mapping(pattern, false)
+        }
+    }
+
+    fn map_with(xlsx_mapping: XlsxMapping) -> EnhancedConversionMap {
+        let mut enhanced_map = EnhancedConversionMap::new();
+        let mut field_def = EnhancedConversionService::new().create_default_field_definition("server_label", "Server").unwrap();
+        field_def.xlsx_mappings = vec![xlsx_mapping];
+        enhanced_map.field_definitions.insert("server_label".to_string(), field_def);
+        enhanced_map
+    }
+
+    #[test]
+    fn test_regex_mappings_honor_case_sensitivity() {
+        let insensitive = HeaderMatcher::new(&map_with(regex_mapping(r"server\s*name", false))).unwrap();
+        assert_eq!(insensitive.best_match("SERVER NAME").map(|m| m.confidence), Some(0.9));
+
+        let sensitive = HeaderMatcher::new(&map_with(regex_mapping(r"server\s*name", true))).unwrap();
+        assert!(sensitive.best_match("SERVER NAME").is_none());
+        assert!(sensitive.best_match("server name").is_some());
+    }
+
+    #[test]
+    fn test_exact_mapping_beats_token_set_subset_match() {
+        let mut enhanced_map = map_with(XlsxMapping {
+            mapping_type: MappingType::Exact,
+            priority: 50,
+            ..regex_mapping("Port", false)
+        });
+        let mut field_def = EnhancedConversionService::new().create_default_field_definition("switch_ifname", "Switch Port").unwrap();
+        field_def.xlsx_mappings = vec![fuzzy_mapping("Switch Port", SimilarityMetric::TokenSet, 90)];
+        enhanced_map.field_definitions.insert("switch_ifname".to_string(), field_def);
+        let matcher = HeaderMatcher::new(&enhanced_map).unwrap();
+
+        // "Port" is a word subset of "Switch Port", which token_set scores as fully similar
+        let best = matcher.best_match("Port").unwrap();
+        assert_eq!((best.field_name.as_str(), best.confidence), ("server_label", 1.0));
+        let fuzzy = matcher.field_matches("Port").into_iter().find(|m| m.field_name == "switch_ifname").unwrap();
+        assert_eq!(fuzzy.confidence, MAX_FUZZY_CONFIDENCE);
+    }
+
+    #[test]
+    fn test_invalid_regex_is_reported() {
+        let error = HeaderMatcher::new(&map_with(regex_mapping("server(name", false))).err().unwrap();
+        assert!(error.contains("server_label") && error.contains("server(name"), "{}", error);
+        assert!(HeaderMatcher::for_map(&map_with(regex_mapping("server(name", false))).is_err());
+    }
+
+    #[test]
+    fn test_matchers_are_cached_by_mappings() {
+        let first = HeaderMatcher::for_map(&map_with(regex_mapping("host.*name", false))).unwrap();
+        let second = HeaderMatcher::for_map(&map_with(regex_mapping("host.*name", false))).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        let changed = HeaderMatcher::for_map(&map_with(regex_mapping("host.*label", false))).unwrap();
+        assert!(!Arc::ptr_eq(&first, &changed));
+        let mut tuned = regex_mapping("host.*name", false);
+        tuned.min_confidence = Some(0.95);
+        let tuned = HeaderMatcher::for_map(&map_with(tuned)).unwrap();
+        assert!(!Arc::ptr_eq(&first, &tuned));
+    }
+}
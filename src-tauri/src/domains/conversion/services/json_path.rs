@@ -0,0 +1,535 @@
+use crate::models::enhanced_conversion_map::DataType;
+use serde_json::Value;
+
+/// A compiled JSONPath expression for extracting values from API responses
+///
+/// Supported syntax: the root `$`, child names (`.name`, `['name']`), array indices (`[0]`,
+/// `[-1]`), wildcards (`.*`, `[*]`), recursive descent (`..name`, `..*`) and filters such as
+/// `[?(@.role=='leaf')]`. Filters compare a relative path with a literal using `==`, `!=`, `<`,
+/// `<=`, `>`, `>=`, test that a path exists (`[?(@.tags)]`), and combine with `&&`, `||`, `!`
+/// and parentheses. A trailing `?` on a name (`$.server.tags?`) is accepted for older maps.
+#[derive(Debug, Clone)]
+pub struct JsonPath {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    Child(Selector),
+    Descendant(Selector),
+}
+
+#[derive(Debug, Clone)]
+enum Selector {
+    Name(String),
+    Index(i64),
+    Wildcard,
+    Filter(FilterExpr),
+}
+
+#[derive(Debug, Clone)]
+enum FilterExpr {
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Not(Box<FilterExpr>),
+    Exists(Vec<Selector>),
+    Compare(Operand, Comparison, Operand),
+}
+
+#[derive(Debug, Clone)]
+enum Operand {
+    Path(Vec<Selector>), // Relative to the current node (@)
+    Literal(Value),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl JsonPath {
+    pub fn parse(path: &str) -> Result<Self, String> {
+        let mut parser = PathParser { chars: path.trim().chars().collect(), pos: 0 };
+        let segments = parser.parse_path()
+            .map_err(|e| format!("Invalid JSONPath '{}': {}", path, e))?;
+        Ok(Self { segments })
+    }
+
+    /// True when the path can select at most one value (no wildcard, filter or recursive descent)
+    pub fn is_singular(&self) -> bool {
+        self.segments.iter().all(|segment| matches!(segment, Segment::Child(Selector::Name(_) | Selector::Index(_))))
+    }
+
+    /// Every value the path selects, in document order
+    pub fn select<'a>(&self, data: &'a Value) -> Vec<&'a Value> {
+        let mut nodes = vec![data];
+        for segment in &self.segments {
+            nodes = match segment {
+                Segment::Child(selector) => nodes.into_iter()
+                    .flat_map(|node| apply_selector(selector, node))
+                    .collect(),
+                Segment::Descendant(selector) => nodes.into_iter()
+                    .flat_map(descendants_or_self)
+                    .flat_map(|node| apply_selector(selector, node))
+                    .collect(),
+            };
+        }
+        nodes
+    }
+
+    /// Extract a field value, aggregating values that come from arrays by the field's data type
+    ///
+    /// A singular path returns the selected value. When several values are selected, or a singular
+    /// path selects an array for a non-array field, the values are aggregated: `array` and `json`
+    /// fields keep a JSON array, `string` fields join the values with ", ", `number` fields take the
+    /// first number and `boolean` fields are true when any value is true. Nulls are skipped, and
+    /// nothing selected (or only nulls) yields None.
+    pub fn extract(&self, data: &Value, data_type: &DataType) -> Option<Value> {
+        let nodes = self.select(data);
+        let values: Vec<&Value> = if self.is_singular() {
+            match nodes.first() {
+                None | Some(Value::Null) => return None,
+                Some(Value::Array(items)) if !matches!(data_type, DataType::Array | DataType::Json) => items.iter().collect(),
+                Some(value) => return Some((*value).clone()),
+            }
+        } else {
+            nodes
+        };
+        aggregate_values(values.into_iter().filter(|value| !value.is_null()).collect(), data_type)
+    }
+}
+
+fn aggregate_values(values: Vec<&Value>, data_type: &DataType) -> Option<Value> {
+    if values.is_empty() {
+        return None;
+    }
+    match data_type {
+        DataType::Array | DataType::Json => Some(Value::Array(values.into_iter().cloned().collect())),
+        DataType::String => Some(Value::String(values.into_iter()
+            .map(|value| match value {
+                Value::String(text) => text.clone(),
+                other => other.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(", "))),
+        DataType::Number => values.into_iter().find(|value| value.is_number()).cloned(),
+        DataType::Boolean => Some(Value::Bool(values.into_iter().any(|value| value.as_bool() == Some(true)))),
+    }
+}
+
+fn apply_selector<'a>(selector: &Selector, node: &'a Value) -> Vec<&'a Value> {
+    match selector {
+        Selector::Name(name) => node.get(name.as_str()).into_iter().collect(),
+        Selector::Index(index) => {
+            let Some(items) = node.as_array() else {
+                return Vec::new();
+            };
+            let index = if *index < 0 { items.len() as i64 + index } else { *index };
+            usize::try_from(index).ok().and_then(|index| items.get(index)).into_iter().collect()
+        }
+        Selector::Wildcard => children(node),
+        Selector::Filter(filter) => children(node).into_iter()
+            .filter(|child| filter.matches(child))
+            .collect(),
+    }
+}
+
+fn children(node: &Value) -> Vec<&Value> {
+    match node {
+        Value::Array(items) => items.iter().collect(),
+        Value::Object(fields) => fields.values().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The node and all its descendants in document order: each node precedes its children
+fn descendants_or_self(node: &Value) -> Vec<&Value> {
+    let mut nodes = Vec::new();
+    let mut stack = vec![node];
+    while let Some(node) = stack.pop() {
+        nodes.push(node);
+        stack.extend(children(node).into_iter().rev());
+    }
+    nodes
+}
+
+/// The single value a relative filter path selects
+fn select_relative<'a>(selectors: &[Selector], node: &'a Value) -> Option<&'a Value> {
+    let mut current = node;
+    for selector in selectors {
+        current = *apply_selector(selector, current).first()?;
+    }
+    Some(current)
+}
+
+impl FilterExpr {
+    fn matches(&self, node: &Value) -> bool {
+        match self {
+            FilterExpr::Or(left, right) => left.matches(node) || right.matches(node),
+            FilterExpr::And(left, right) => left.matches(node) && right.matches(node),
+            FilterExpr::Not(expr) => !expr.matches(node),
+            FilterExpr::Exists(selectors) => select_relative(selectors, node).is_some_and(|value| !value.is_null()),
+            FilterExpr::Compare(left, comparison, right) => {
+                let (Some(left), Some(right)) = (left.resolve(node), right.resolve(node)) else {
+                    return false;
+                };
+                compare_values(left, *comparison, right)
+            }
+        }
+    }
+}
+
+impl Operand {
+    fn resolve<'a>(&'a self, node: &'a Value) -> Option<&'a Value> {
+        match self {
+            Operand::Path(selectors) => select_relative(selectors, node),
+            Operand::Literal(value) => Some(value),
+        }
+    }
+}
+
+fn compare_values(left: &Value, comparison: Comparison, right: &Value) -> bool {
+    let ordering = match (left, right) {
+        (Value::Number(left), Value::Number(right)) => left.as_f64().zip(right.as_f64())
+            .and_then(|(left, right)| left.partial_cmp(&right)),
+        (Value::String(left), Value::String(right)) => Some(left.cmp(right)),
+        _ => None,
+    };
+    match comparison {
+        Comparison::Equal => ordering.map_or(left == right, |ordering| ordering.is_eq()),
+        Comparison::NotEqual => ordering.map_or(left != right, |ordering| ordering.is_ne()),
+        Comparison::Less => ordering.is_some_and(|ordering| ordering.is_lt()),
+        Comparison::LessOrEqual => ordering.is_some_and(|ordering| ordering.is_le()),
+        Comparison::Greater => ordering.is_some_and(|ordering| ordering.is_gt()),
+        Comparison::GreaterOrEqual => ordering.is_some_and(|ordering| ordering.is_ge()),
+    }
+}
+
+struct PathParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl PathParser {
+    fn parse_path(&mut self) -> Result<Vec<Segment>, String> {
+        let mut segments = Vec::new();
+        if self.eat('$') {
+            // Explicit root
+        } else if !matches!(self.peek(), Some('.') | Some('[') | None) {
+            // Older maps may leave out the root: "server.tags"
+            segments.push(Segment::Child(self.parse_dot_selector()?));
+        }
+
+        while let Some(c) = self.peek() {
+            match c {
+                '.' if self.peek_at(1) == Some('.') => {
+                    self.pos += 2;
+                    let selector = if self.peek() == Some('[') {
+                        self.parse_bracket_selector()?
+                    } else {
+                        self.parse_dot_selector()?
+                    };
+                    segments.push(Segment::Descendant(selector));
+                }
+                '.' => {
+                    self.pos += 1;
+                    segments.push(Segment::Child(self.parse_dot_selector()?));
+                }
+                '[' => segments.push(Segment::Child(self.parse_bracket_selector()?)),
+                _ => return Err(format!("unexpected '{}' at position {}", c, self.pos)),
+            }
+        }
+        Ok(segments)
+    }
+
+    /// A selector after a dot: `*` or a name up to the next `.` or `[`
+    fn parse_dot_selector(&mut self) -> Result<Selector, String> {
+        if self.eat('*') {
+            return Ok(Selector::Wildcard);
+        }
+        let start = self.pos;
+        while matches!(self.peek(), Some(c) if c != '.' && c != '[') {
+            self.pos += 1;
+        }
+        let name: String = self.chars[start..self.pos].iter().collect();
+        let name = name.strip_suffix('?').unwrap_or(&name).trim();
+        if name.is_empty() {
+            return Err(format!("expected a name at position {}", start));
+        }
+        Ok(Selector::Name(name.to_string()))
+    }
+
+    fn parse_bracket_selector(&mut self) -> Result<Selector, String> {
+        self.expect('[')?;
+        self.skip_whitespace();
+        let selector = match self.peek() {
+            Some('*') => {
+                self.pos += 1;
+                Selector::Wildcard
+            }
+            Some('?') => {
+                self.pos += 1;
+                self.skip_whitespace();
+                self.expect('(')?;
+                let filter = self.parse_or()?;
+                self.skip_whitespace();
+                self.expect(')')?;
+                Selector::Filter(filter)
+            }
+            Some('\'') | Some('"') => Selector::Name(self.parse_quoted()?),
+            _ => Selector::Index(self.parse_integer()?),
+        };
+        self.skip_whitespace();
+        self.expect(']')?;
+        Ok(selector)
+    }
+
+    fn parse_or(&mut self) -> Result<FilterExpr, String> {
+        let mut expr = self.parse_and()?;
+        while self.eat_token("||") {
+            expr = FilterExpr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<FilterExpr, String> {
+        let mut expr = self.parse_unary()?;
+        while self.eat_token("&&") {
+            expr = FilterExpr::And(Box::new(expr), Box::new(self.parse_unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_unary(&mut self) -> Result<FilterExpr, String> {
+        self.skip_whitespace();
+        if self.peek() == Some('!') && self.peek_at(1) != Some('=') {
+            self.pos += 1;
+            return Ok(FilterExpr::Not(Box::new(self.parse_unary()?)));
+        }
+        if self.eat('(') {
+            let expr = self.parse_or()?;
+            self.skip_whitespace();
+            self.expect(')')?;
+            return Ok(expr);
+        }
+
+        let left = self.parse_operand()?;
+        let comparison = [
+            ("==", Comparison::Equal),
+            ("!=", Comparison::NotEqual),
+            ("<=", Comparison::LessOrEqual),
+            (">=", Comparison::GreaterOrEqual),
+            ("<", Comparison::Less),
+            (">", Comparison::Greater),
+        ]
+            .into_iter()
+            .find(|(token, _)| self.eat_token(token))
+            .map(|(_, comparison)| comparison);
+
+        match (comparison, left) {
+            (Some(comparison), left) => Ok(FilterExpr::Compare(left, comparison, self.parse_operand()?)),
+            (None, Operand::Path(selectors)) => Ok(FilterExpr::Exists(selectors)),
+            (None, Operand::Literal(_)) => Err(format!("expected a comparison at position {}", self.pos)),
+        }
+    }
+
+    fn parse_operand(&mut self) -> Result<Operand, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('@') => {
+                self.pos += 1;
+                let mut selectors = Vec::new();
+                loop {
+                    match self.peek() {
+                        Some('.') => {
+                            self.pos += 1;
+                            let start = self.pos;
+                            while matches!(self.peek(), Some(c) if c.is_alphanumeric() || c == '_' || c == '-') {
+                                self.pos += 1;
+                            }
+                            if start == self.pos {
+                                return Err(format!("expected a name at position {}", start));
+                            }
+                            selectors.push(Selector::Name(self.chars[start..self.pos].iter().collect()));
+                        }
+                        Some('[') => {
+                            let selector = self.parse_bracket_selector()?;
+                            if matches!(selector, Selector::Wildcard | Selector::Filter(_)) {
+                                return Err("filter paths must select a single value".to_string());
+                            }
+                            selectors.push(selector);
+                        }
+                        _ => break,
+                    }
+                }
+                Ok(Operand::Path(selectors))
+            }
+            Some('\'') | Some('"') => Ok(Operand::Literal(Value::String(self.parse_quoted()?))),
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                let start = self.pos;
+                self.pos += 1;
+                while matches!(self.peek(), Some(c) if c.is_ascii_digit() || c == '.' || c == 'e' || c == 'E') {
+                    self.pos += 1;
+                }
+                let literal: String = self.chars[start..self.pos].iter().collect();
+                serde_json::from_str::<serde_json::Number>(&literal)
+                    .map(|number| Operand::Literal(Value::Number(number)))
+                    .map_err(|_| format!("invalid number '{}'", literal))
+            }
+            _ => {
+                for (token, value) in [("true", Value::Bool(true)), ("false", Value::Bool(false)), ("null", Value::Null)] {
+                    if self.eat_token(token) {
+                        return Ok(Operand::Literal(value));
+                    }
+                }
+                Err(format!("expected @, a string, a number, true, false or null at position {}", self.pos))
+            }
+        }
+    }
+
+    fn parse_quoted(&mut self) -> Result<String, String> {
+        let quote = self.peek().ok_or("expected a quoted string")?;
+        self.pos += 1;
+        let mut text = String::new();
+        loop {
+            match self.peek() {
+                None => return Err("unterminated string".to_string()),
+                Some('\\') => {
+                    self.pos += 1;
+                    text.push(self.peek().ok_or("unterminated string")?);
+                }
+                Some(c) if c == quote => break,
+                Some(c) => text.push(c),
+            }
+            self.pos += 1;
+        }
+        self.pos += 1;
+        Ok(text)
+    }
+
+    fn parse_integer(&mut self) -> Result<i64, String> {
+        let start = self.pos;
+        if self.peek() == Some('-') {
+            self.pos += 1;
+        }
+        while matches!(self.peek(), Some(c) if c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        let literal: String = self.chars[start..self.pos].iter().collect();
+        literal.parse().map_err(|_| format!("expected an index, *, a quoted name or a filter at position {}", start))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_token(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        let matches = token.chars().enumerate().all(|(offset, c)| self.peek_at(offset) == Some(c));
+        if matches {
+            self.pos += token.chars().count();
+        }
+        matches
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(format!("expected '{}' at position {}", c, self.pos))
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(c) if c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn blueprint_nodes() -> Value {
+        json!({
+            "label": "rack-a",
+            "systems": [
+                {"label": "leaf-01", "role": "leaf", "tags": ["prod", "rack-a"], "ports": 48},
+                {"label": "spine-01", "role": "spine", "tags": ["core"], "ports": 32},
+                {"label": "leaf-02", "role": "leaf", "tags": [], "ports": 96}
+            ],
+            "connectivity_templates": [
+                {"name": "CT-PROD", "vlan": {"id": 100}},
+                {"name": null},
+                {"name": "CT-MGMT", "vlan": {"id": 200}}
+            ]
+        })
+    }
+
+    fn select(path: &str) -> Vec<Value> {
+        JsonPath::parse(path).unwrap().select(&blueprint_nodes()).into_iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_names_indices_and_wildcards() {
+        assert_eq!(select("$.label"), vec![json!("rack-a")]);
+        assert_eq!(select("$.systems[1].label"), vec![json!("spine-01")]);
+        assert_eq!(select("$.systems[-1]['label']"), vec![json!("leaf-02")]);
+        assert_eq!(select("$.systems[*].label"), vec![json!("leaf-01"), json!("spine-01"), json!("leaf-02")]);
+        assert_eq!(select("$.systems.*.ports"), vec![json!(48), json!(32), json!(96)]);
+        assert!(select("$.systems[7].label").is_empty());
+        assert_eq!(select("label"), vec![json!("rack-a")]);
+    }
+
+    #[test]
+    fn test_filters_and_recursive_descent() {
+        assert_eq!(select("$.systems[?(@.role=='leaf')].label"), vec![json!("leaf-01"), json!("leaf-02")]);
+        assert_eq!(select("$.systems[?(@.role == 'leaf' && @.ports > 50)].label"), vec![json!("leaf-02")]);
+        assert_eq!(select("$.systems[?(!(@.role == 'leaf') || @.ports <= 48)].label"), vec![json!("leaf-01"), json!("spine-01")]);
+        assert_eq!(select("$.connectivity_templates[?(@.vlan)].name"), vec![json!("CT-PROD"), json!("CT-MGMT")]);
+        assert_eq!(select("$..vlan.id"), vec![json!(100), json!(200)]);
+        assert_eq!(select("$..systems[0].label"), vec![json!("leaf-01")]);
+
+        let nested = json!({"pod": {"rack": {"id": 1}}, "spine": {"id": 2}});
+        let ids: Vec<&Value> = JsonPath::parse("$..id").unwrap().select(&nested);
+        assert_eq!(ids, vec![&json!(1), &json!(2)]);
+
+        assert!(JsonPath::parse("$.systems[?(@.role==)]").is_err());
+        assert!(JsonPath::parse("$.systems[").is_err());
+    }
+
+    #[test]
+    fn test_extract_aggregates_by_data_type() {
+        let data = blueprint_nodes();
+        let extract = |path: &str, data_type: DataType| JsonPath::parse(path).unwrap().extract(&data, &data_type);
+
+        assert_eq!(extract("$.connectivity_templates[*].name", DataType::String), Some(json!("CT-PROD, CT-MGMT")));
+        assert_eq!(extract("$.connectivity_templates[*].name", DataType::Array), Some(json!(["CT-PROD", "CT-MGMT"])));
+        assert_eq!(extract("$.systems[0].tags", DataType::String), Some(json!("prod, rack-a")));
+        assert_eq!(extract("$.systems[0].tags", DataType::Array), Some(json!(["prod", "rack-a"])));
+        assert_eq!(extract("$.systems[*].ports", DataType::Number), Some(json!(48)));
+        assert_eq!(extract("$.systems[?(@.role=='border')].label", DataType::String), None);
+        assert_eq!(extract("$.connectivity_templates[1].name", DataType::String), None);
+        assert_eq!(extract("$.label", DataType::String), Some(json!("rack-a")));
+    }
+}
@@ -1,5 +1,8 @@
 // Conversion services module
 pub mod enhanced_conversion_service;
+pub mod header_matcher;
+pub mod json_path;
+pub mod similarity;
 pub mod transformation_engine;
 
 pub use enhanced_conversion_service::*;
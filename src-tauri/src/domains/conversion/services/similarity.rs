@@ -0,0 +1,144 @@
+//! String similarity metrics for fuzzy header matching
+//!
+//! All metrics work on Unicode characters rather than bytes, so accented or Japanese headers
+//! are compared letter by letter. Scores range from 0.0 (nothing in common) to 1.0 (equal).
+
+/// Levenshtein edit distance counted in characters, using two rows of O(n) memory
+pub fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let cost = if a_char == b_char { 0 } else { 1 };
+            current[j + 1] = (previous[j + 1] + 1)
+                .min(current[j] + 1)
+                .min(previous[j] + cost);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Edit distance scaled by the longer string's length in characters
+pub fn levenshtein_similarity(a: &str, b: &str) -> f64 {
+    let max_len = a.chars().count().max(b.chars().count());
+    if max_len == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein_distance(a, b) as f64 / max_len as f64
+}
+
+/// Jaro-Winkler similarity, which favours strings sharing a prefix such as "Srv" and "Server"
+pub fn jaro_winkler(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let jaro = jaro(&a, &b);
+    let prefix = a.iter().zip(&b)
+        .take(4)
+        .take_while(|(a_char, b_char)| a_char == b_char)
+        .count();
+    jaro + prefix as f64 * 0.1 * (1.0 - jaro)
+}
+
+fn jaro(a: &[char], b: &[char]) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut b_matched = vec![false; b.len()];
+    let mut a_matches = Vec::new();
+    for (i, a_char) in a.iter().enumerate() {
+        let start = i.saturating_sub(window);
+        let end = (i + window + 1).min(b.len());
+        if let Some(j) = (start..end).find(|&j| !b_matched[j] && b[j] == *a_char) {
+            b_matched[j] = true;
+            a_matches.push(*a_char);
+        }
+    }
+    if a_matches.is_empty() {
+        return 0.0;
+    }
+
+    let b_matches = b.iter().zip(&b_matched).filter(|(_, matched)| **matched).map(|(b_char, _)| b_char);
+    let transpositions = a_matches.iter().zip(b_matches).filter(|(a_char, b_char)| a_char != b_char).count() / 2;
+    let matches = a_matches.len() as f64;
+    (matches / a.len() as f64 + matches / b.len() as f64 + (matches - transpositions as f64) / matches) / 3.0
+}
+
+/// Token-set ratio: word order and repeated words are ignored, and shared words count in full
+///
+/// The shared words are compared with the shared words plus each side's remaining words, and
+/// the best Levenshtein similarity of those three strings wins, so "Srv NIC Port" scores well
+/// against "Server NIC Port".
+pub fn token_set_ratio(a: &str, b: &str) -> f64 {
+    let a_tokens = tokens(a);
+    let b_tokens = tokens(b);
+    if a_tokens.is_empty() && b_tokens.is_empty() {
+        return 1.0;
+    }
+
+    let shared: Vec<&str> = a_tokens.iter().filter(|token| b_tokens.contains(token)).copied().collect();
+    let a_rest: Vec<&str> = a_tokens.iter().filter(|token| !b_tokens.contains(token)).copied().collect();
+    let b_rest: Vec<&str> = b_tokens.iter().filter(|token| !a_tokens.contains(token)).copied().collect();
+
+    let shared_text = shared.join(" ");
+    let a_text = [shared.as_slice(), a_rest.as_slice()].concat().join(" ");
+    let b_text = [shared.as_slice(), b_rest.as_slice()].concat().join(" ");
+
+    let mut best = levenshtein_similarity(&a_text, &b_text);
+    if !shared.is_empty() {
+        best = best
+            .max(levenshtein_similarity(&shared_text, &a_text))
+            .max(levenshtein_similarity(&shared_text, &b_text));
+    }
+    best
+}
+
+/// Sorted, distinct words split on anything that is not a letter or digit
+fn tokens(text: &str) -> Vec<&str> {
+    let mut tokens: Vec<&str> = text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .collect();
+    tokens.sort_unstable();
+    tokens.dedup();
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levenshtein_counts_characters_not_bytes() {
+        assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
+        assert_eq!(levenshtein_distance("", "port"), 4);
+        assert_eq!(levenshtein_distance("ポート番号", "ポート"), 2);
+        assert_eq!(levenshtein_distance("Région", "Region"), 1);
+        assert_eq!(levenshtein_similarity("ポート", "ポート"), 1.0);
+        assert_eq!(levenshtein_similarity("", ""), 1.0);
+    }
+
+    #[test]
+    fn test_jaro_winkler() {
+        assert!((jaro_winkler("martha", "marhta") - 0.961).abs() < 0.001);
+        assert!((jaro_winkler("dixon", "dicksonx") - 0.813).abs() < 0.001);
+        assert_eq!(jaro_winkler("switch", "switch"), 1.0);
+        assert_eq!(jaro_winkler("abc", "xyz"), 0.0);
+    }
+
+    #[test]
+    fn test_token_set_ratio_ignores_order_and_rewards_shared_words() {
+        assert_eq!(token_set_ratio("port switch", "switch port"), 1.0);
+        assert_eq!(token_set_ratio("switch / port", "port switch switch"), 1.0);
+        assert!(token_set_ratio("srv nic port", "server nic port") >= 0.8);
+        assert!(token_set_ratio("srv nic port", "switch name") < 0.5);
+    }
+}
@@ -1,9 +1,19 @@
 use crate::models::enhanced_conversion_map::{
-    TransformationRule, TransformationLogic
+    TransformationRule, TransformationLogic, Condition, CompareOp, ArrayFormat
 };
+use regex::Regex;
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use serde_json::Value;
 
+/// Compiled patterns kept before one is evicted
+const REGEX_CACHE_CAPACITY: usize = 256;
+
+lazy_static::lazy_static! {
+    /// Compiled rule patterns, or their compile error, by pattern
+    static ref REGEX_CACHE: Mutex<HashMap<String, Result<Arc<Regex>, String>>> = Mutex::new(HashMap::new());
+}
+
 type TransformationFunction = Box<dyn Fn(&str, Option<&HashMap<String, String>>) -> Result<String, String> + Send + Sync>;
 
 pub struct TransformationEngine {
@@ -88,7 +98,16 @@ impl TransformationEngine {
             }
         }
 
-        match &rule.logic {
+        self.apply_logic(&rule.logic, input, context)
+    }
+
+    fn apply_logic(
+        &self,
+        logic: &TransformationLogic,
+        input: &str,
+        context: Option<&HashMap<String, String>>
+    ) -> Result<String, String> {
+        match logic {
             TransformationLogic::ValueMap { mappings: value_map } => {
                 // Direct value mapping
                 if let Some(mapped_value) = value_map.get(input) {
@@ -117,16 +136,110 @@ impl TransformationEngine {
                 }
             }
             TransformationLogic::Pipeline { steps } => {
-                // Multi-step transformation pipeline
+                // Multi-step transformation pipeline; named regex_extract captures become
+                // placeholders for the steps after it
                 let mut current_value = input.to_string();
+                let mut pipeline_context: Option<HashMap<String, String>> = None;
                 for step in steps {
-                    current_value = self.apply_transformation_step(&step.step_type, &current_value, &step.parameters, context)?;
+                    let step_context = pipeline_context.as_ref().or(context);
+                    if step.step_type == "regex_extract" {
+                        let (value, captures) = self.apply_regex_extract(&current_value, &step.parameters, step_context)?;
+                        current_value = value;
+                        pipeline_context.get_or_insert_with(|| context.cloned().unwrap_or_default()).extend(captures);
+                    } else {
+                        current_value = self.apply_transformation_step(&step.step_type, &current_value, &step.parameters, step_context)?;
+                    }
                 }
                 Ok(current_value)
             }
+            TransformationLogic::Conditional { branches, otherwise } => {
+                for branch in branches {
+                    if self.evaluate_condition(&branch.condition, input, context)? {
+                        return self.apply_logic(&branch.then, input, context);
+                    }
+                }
+                match otherwise {
+                    Some(logic) => self.apply_logic(logic, input, context),
+                    None => Ok(input.to_string()),
+                }
+            }
         }
     }
 
+    /// Evaluate a condition against the input value, or against the context field it names
+    fn evaluate_condition(
+        &self,
+        condition: &Condition,
+        input: &str,
+        context: Option<&HashMap<String, String>>
+    ) -> Result<bool, String> {
+        let value_of = |field: &Option<String>| -> String {
+            match field {
+                Some(field_name) => context
+                    .and_then(|ctx| ctx.get(field_name))
+                    .cloned()
+                    .unwrap_or_default(),
+                None => input.to_string(),
+            }
+        };
+
+        Ok(match condition {
+            Condition::Equals { field, value: expected, case_sensitive } => {
+                let value = value_of(field);
+                let value = value.trim();
+                if *case_sensitive { value == expected } else { value.to_lowercase() == expected.to_lowercase() }
+            }
+            Condition::Matches { field, pattern } => {
+                let regex = cached_regex(pattern)
+                    .map_err(|e| format!("Invalid condition pattern '{}': {}", pattern, e))?;
+                regex.is_match(&value_of(field))
+            }
+            Condition::InSet { field, values, case_sensitive } => {
+                let value = value_of(field);
+                let value = value.trim();
+                if *case_sensitive {
+                    values.iter().any(|candidate| candidate == value)
+                } else {
+                    values.iter().any(|candidate| candidate.to_lowercase() == value.to_lowercase())
+                }
+            }
+            Condition::Empty { field } => value_of(field).trim().is_empty(),
+            Condition::Truthy { field } => {
+                matches!(value_of(field).trim().to_lowercase().as_str(), "true" | "yes" | "y" | "1")
+            }
+            Condition::Compare { field, op, value: expected } => {
+                match value_of(field).trim().parse::<f64>() {
+                    Ok(value) => match op {
+                        CompareOp::Eq => value == *expected,
+                        CompareOp::Ne => value != *expected,
+                        CompareOp::Lt => value < *expected,
+                        CompareOp::Le => value <= *expected,
+                        CompareOp::Gt => value > *expected,
+                        CompareOp::Ge => value >= *expected,
+                    },
+                    Err(_) => false,
+                }
+            }
+            Condition::And { conditions } => {
+                for condition in conditions {
+                    if !self.evaluate_condition(condition, input, context)? {
+                        return Ok(false);
+                    }
+                }
+                true
+            }
+            Condition::Or { conditions } => {
+                for condition in conditions {
+                    if self.evaluate_condition(condition, input, context)? {
+                        return Ok(true);
+                    }
+                }
+                false
+            }
+            Condition::Not { condition } => !self.evaluate_condition(condition, input, context)?,
+        })
+    }
+
     fn evaluate_conditions(
         &self, 
         conditions: &HashMap<String, Value>, 
@@ -236,10 +349,74 @@ impl TransformationEngine {
                     Err("Value map step missing 'mappings' parameter".to_string())
                 }
             }
+            "regex_replace" => {
+                let regex = Self::step_regex(parameters)?;
+                let replacement = parameters.get("replacement").and_then(|v| v.as_str())
+                    .ok_or_else(|| "Regex replace step missing 'replacement' parameter".to_string())?;
+                Ok(regex.replace_all(input, replacement).to_string())
+            }
+            "regex_extract" => {
+                self.apply_regex_extract(input, parameters, context).map(|(value, _)| value)
+            }
+            "list" => Ok(Self::step_array_format(parameters)?.normalize(input)),
             _ => Err(format!("Unknown transformation step type: {}", step_type))
         }
     }
 
+    /// Extract from the first regex match, returning the value and the named captures
+    /// 
+    /// With a `template`, named captures are its placeholders; otherwise the first capture group,
+    /// or the whole match without groups, is the value. Without a match the value is `default`,
+    /// or the input unchanged. Named groups that did not match capture an empty string.
+    fn apply_regex_extract(
+        &self,
+        input: &str,
+        parameters: &HashMap<String, Value>,
+        context: Option<&HashMap<String, String>>
+    ) -> Result<(String, HashMap<String, String>), String> {
+        let regex = Self::step_regex(parameters)?;
+        let captures = regex.captures(input);
+        let named_captures: HashMap<String, String> = regex.capture_names()
+            .flatten()
+            .map(|name| {
+                let value = captures.as_ref().and_then(|captures| captures.name(name)).map(|m| m.as_str()).unwrap_or_default();
+                (name.to_string(), value.to_string())
+            })
+            .collect();
+        let Some(captures) = captures else {
+            let default = parameters.get("default").and_then(|v| v.as_str()).unwrap_or(input);
+            return Ok((default.to_string(), named_captures));
+        };
+
+        let value = match parameters.get("template").and_then(|v| v.as_str()) {
+            Some(template) => {
+                let mut template_context = context.cloned().unwrap_or_default();
+                template_context.extend(named_captures.clone());
+                self.apply_template_transformation(template, input, Some(&template_context))?
+            }
+            None => captures.get(1).or_else(|| captures.get(0))
+                .map(|m| m.as_str().to_string())
+                .unwrap_or_default(),
+        };
+        Ok((value, named_captures))
+    }
+
+    /// Array format of a list step; parameters it leaves out keep their defaults
+    fn step_array_format(parameters: &HashMap<String, Value>) -> Result<ArrayFormat, String> {
+        let parameters: serde_json::Map<String, Value> = parameters.iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        serde_json::from_value(Value::Object(parameters))
+            .map_err(|e| format!("Invalid list step parameters: {}", e))
+    }
+
+    fn step_regex(parameters: &HashMap<String, Value>) -> Result<Arc<Regex>, String> {
+        let pattern = parameters.get("pattern").and_then(|v| v.as_str())
+            .ok_or_else(|| "Regex step missing 'pattern' parameter".to_string())?;
+        cached_regex(pattern)
+            .map_err(|e| format!("Invalid regex step pattern '{}': {}", pattern, e))
+    }
+
     fn is_numeric_port(input: &str) -> bool {
         input.trim().parse::<u32>().is_ok()
     }
@@ -368,8 +545,42 @@ impl TransformationEngine {
         self.functions.insert(name, Box::new(function));
     }
 
+    /// Compile the patterns of a rule's conditions and regex steps once, so an invalid pattern
+    /// fails when the map is loaded instead of on every value
+    pub fn compile_rule_patterns(rule: &TransformationRule) -> Result<(), String> {
+        Self::compile_logic_patterns(&rule.logic)
+            .map_err(|e| format!("Transformation rule '{}': {}", rule.name, e))
+    }
+
+    fn compile_logic_patterns(logic: &TransformationLogic) -> Result<(), String> {
+        match logic {
+            TransformationLogic::Pipeline { steps } => {
+                for step in steps {
+                    if matches!(step.step_type.as_str(), "regex_replace" | "regex_extract") {
+                        Self::step_regex(&step.parameters)?;
+                    }
+                }
+            }
+            TransformationLogic::Conditional { branches, otherwise } => {
+                for branch in branches {
+                    Self::validate_condition(&branch.condition)?;
+                    Self::compile_logic_patterns(&branch.then)?;
+                }
+                if let Some(logic) = otherwise {
+                    Self::compile_logic_patterns(logic)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
     pub fn validate_transformation_rule(&self, rule: &TransformationRule) -> Result<(), String> {
-        match &rule.logic {
+        self.validate_logic(&rule.logic)
+    }
+
+    fn validate_logic(&self, logic: &TransformationLogic) -> Result<(), String> {
+        match logic {
             TransformationLogic::Function { name: function_name } => {
                 if !self.functions.contains_key(function_name) {
                     return Err(format!("Unknown transformation function: {}", function_name));
@@ -411,17 +622,78 @@ impl TransformationEngine {
                                 return Err("Value map step missing 'mappings' parameter".to_string());
                             }
                         }
+                        "regex_replace" => {
+                            Self::step_regex(&step.parameters)?;
+                            if step.parameters.get("replacement").and_then(|v| v.as_str()).is_none() {
+                                return Err("Regex replace step missing 'replacement' parameter".to_string());
+                            }
+                        }
+                        "regex_extract" => {
+                            Self::step_regex(&step.parameters)?;
+                        }
+                        "list" => {
+                            Self::step_array_format(&step.parameters)?;
+                        }
                         _ => {
                             return Err(format!("Unknown transformation step type: {}", step.step_type));
                         }
                     }
                 }
             }
+            TransformationLogic::Conditional { branches, otherwise } => {
+                if branches.is_empty() {
+                    return Err("Conditional needs at least one branch".to_string());
+                }
+                for branch in branches {
+                    Self::validate_condition(&branch.condition)?;
+                    self.validate_logic(&branch.then)?;
+                }
+                if let Some(logic) = otherwise {
+                    self.validate_logic(logic)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_condition(condition: &Condition) -> Result<(), String> {
+        match condition {
+            Condition::Matches { pattern, .. } => {
+                cached_regex(pattern)
+                    .map_err(|e| format!("Invalid condition pattern '{}': {}", pattern, e))?;
+            }
+            Condition::And { conditions } | Condition::Or { conditions } => {
+                if conditions.is_empty() {
+                    return Err("and/or condition needs at least one condition".to_string());
+                }
+                for condition in conditions {
+                    Self::validate_condition(condition)?;
+                }
+            }
+            Condition::Not { condition } => Self::validate_condition(condition)?,
+            _ => {}
         }
         Ok(())
     }
 }
 
+/// Compile a pattern on first use and reuse it; an invalid pattern keeps its compile error
+fn cached_regex(pattern: &str) -> Result<Arc<Regex>, String> {
+    let mut cache = REGEX_CACHE.lock()
+        .map_err(|_| "Failed to acquire regex cache lock".to_string())?;
+    if let Some(compiled) = cache.get(pattern) {
+        return compiled.clone();
+    }
+    let compiled = Regex::new(pattern).map(Arc::new).map_err(|e| e.to_string());
+    if cache.len() >= REGEX_CACHE_CAPACITY {
+        if let Some(evicted) = cache.keys().next().cloned() {
+            cache.remove(&evicted);
+        }
+    }
+    cache.insert(pattern.to_string(), compiled.clone());
+    compiled
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -525,4 +797,218 @@ mod tests {
         let result = engine.apply_transformation(&rule, "input", Some(&context));
         assert_eq!(result.unwrap(), "et-0/0/5");
     }
+
+    fn conditional_rule(logic: serde_json::Value) -> TransformationRule {
+        TransformationRule {
+            name: "test_conditional".to_string(),
+            description: "Test conditional".to_string(),
+            rule_type: TransformationType::Conditional,
+            conditions: None,
+            logic: serde_json::from_value(logic).expect("Conditional logic should parse"),
+            priority: 1,
+        }
+    }
+
+    #[test]
+    fn test_conditional_transformation() {
+        let engine = TransformationEngine::new();
+        let rule = conditional_rule(serde_json::json!({
+            "type": "conditional",
+            "branches": [
+                {
+                    "if": { "type": "truthy", "field": "is_external" },
+                    "then": { "type": "template", "template": "ext-{input}" }
+                },
+                {
+                    "if": { "type": "and", "conditions": [
+                        { "type": "matches", "pattern": "^srv" },
+                        { "type": "not", "condition": { "type": "empty", "field": "link_speed" } },
+                        { "type": "compare", "field": "rack", "op": "ge", "value": 10 }
+                    ] },
+                    "then": { "type": "function", "name": "to_uppercase" }
+                }
+            ],
+            "else": { "type": "value_map", "mappings": { "db-01": "database-01" } }
+        }));
+        engine.validate_transformation_rule(&rule).unwrap();
+
+        let context = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+            pairs.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect()
+        };
+        let external = context(&[("is_external", "Yes")]);
+        assert_eq!(engine.apply_transformation(&rule, "srv-01", Some(&external)).unwrap(), "ext-srv-01");
+
+        let rack_12 = context(&[("is_external", "no"), ("link_speed", "25G"), ("rack", "12")]);
+        assert_eq!(engine.apply_transformation(&rule, "srv-01", Some(&rack_12)).unwrap(), "SRV-01");
+
+        // A non-numeric rack fails the comparison and falls through to else
+        let rack_unknown = context(&[("link_speed", "25G"), ("rack", "A")]);
+        assert_eq!(engine.apply_transformation(&rule, "srv-01", Some(&rack_unknown)).unwrap(), "srv-01");
+        assert_eq!(engine.apply_transformation(&rule, "db-01", None).unwrap(), "database-01");
+    }
+
+    #[test]
+    fn test_conditional_in_set_equals_and_or() {
+        let engine = TransformationEngine::new();
+        let rule = conditional_rule(serde_json::json!({
+            "type": "conditional",
+            "branches": [{
+                "if": { "type": "or", "conditions": [
+                    { "type": "in_set", "field": "lag_mode", "values": ["lacp_active", "static"] },
+                    { "type": "equals", "value": "Bond0", "case_sensitive": true }
+                ] },
+                "then": { "type": "template", "template": "{input}-lag" }
+            }]
+        }));
+
+        let mut context = HashMap::new();
+        context.insert("lag_mode".to_string(), " LACP_Active ".to_string());
+        assert_eq!(engine.apply_transformation(&rule, "eth0", Some(&context)).unwrap(), "eth0-lag");
+        assert_eq!(engine.apply_transformation(&rule, "Bond0", None).unwrap(), "Bond0-lag");
+        // Without a matching branch or else, the input is unchanged
+        assert_eq!(engine.apply_transformation(&rule, "bond0", None).unwrap(), "bond0");
+    }
+
+    #[test]
+    fn test_conditional_validation() {
+        let engine = TransformationEngine::new();
+        let bad_pattern = conditional_rule(serde_json::json!({
+            "type": "conditional",
+            "branches": [{
+                "if": { "type": "not", "condition": { "type": "matches", "pattern": "eth(" } },
+                "then": { "type": "template", "template": "{input}" }
+            }]
+        }));
+        assert!(engine.validate_transformation_rule(&bad_pattern).unwrap_err().contains("eth("));
+        assert!(engine.apply_transformation(&bad_pattern, "eth0", None).is_err());
+
+        let unknown_function = conditional_rule(serde_json::json!({
+            "type": "conditional",
+            "branches": [{ "if": { "type": "empty" }, "then": { "type": "template", "template": "-" } }],
+            "else": { "type": "function", "name": "no_such_function" }
+        }));
+        assert!(engine.validate_transformation_rule(&unknown_function).is_err());
+    }
+
+    #[test]
+    fn test_condition_patterns_compile_once() {
+        let engine = TransformationEngine::new();
+        let rule = conditional_rule(serde_json::json!({
+            "type": "conditional",
+            "branches": [{
+                "if": { "type": "matches", "field": "switch_label", "pattern": "^spine-\\d+$" },
+                "then": { "type": "template", "template": "{input}-uplink" }
+            }]
+        }));
+        TransformationEngine::compile_rule_patterns(&rule).unwrap();
+        let compiled = cached_regex("^spine-\\d+$").unwrap();
+
+        let mut context = HashMap::new();
+        context.insert("switch_label".to_string(), "spine-01".to_string());
+        assert_eq!(engine.apply_transformation(&rule, "et-0/0/1", Some(&context)).unwrap(), "et-0/0/1-uplink");
+        assert!(Arc::ptr_eq(&compiled, &cached_regex("^spine-\\d+$").unwrap()));
+
+        let bad_pattern = conditional_rule(serde_json::json!({
+            "type": "conditional",
+            "branches": [{ "if": { "type": "matches", "pattern": "spine(" }, "then": { "type": "template", "template": "-" } }]
+        }));
+        let error = TransformationEngine::compile_rule_patterns(&bad_pattern).unwrap_err();
+        assert!(error.contains("test_conditional") && error.contains("spine("), "{}", error);
+    }
+
+    fn pipeline_rule(steps: serde_json::Value) -> TransformationRule {
+        TransformationRule {
+            name: "test_pipeline".to_string(),
+            description: "Test pipeline".to_string(),
+            rule_type: TransformationType::Pipeline,
+            conditions: None,
+            logic: TransformationLogic::Pipeline { steps: serde_json::from_value(steps).expect("Steps should parse") },
+            priority: 1,
+        }
+    }
+
+    #[test]
+    fn test_regex_replace_step() {
+        let engine = TransformationEngine::new();
+        let rule = pipeline_rule(serde_json::json!([
+            { "step_type": "function", "parameters": { "name": "trim_whitespace" } },
+            { "step_type": "regex_replace", "parameters": {
+                "pattern": "^(?i)gi(?:gabitethernet)?\\d+/(?P<pic>\\d+)/(?P<port>\\d+)$",
+                "replacement": "ge-0/${pic}/${port}"
+            } }
+        ]));
+        engine.validate_transformation_rule(&rule).unwrap();
+
+        assert_eq!(engine.apply_transformation(&rule, " Gi1/0/5 ", None).unwrap(), "ge-0/0/5");
+        assert_eq!(engine.apply_transformation(&rule, "GigabitEthernet2/1/48", None).unwrap(), "ge-0/1/48");
+        assert_eq!(engine.apply_transformation(&rule, "xe-0/0/1", None).unwrap(), "xe-0/0/1");
+    }
+
+    #[test]
+    fn test_regex_step_patterns_compile_once() {
+        let engine = TransformationEngine::new();
+        let rule = pipeline_rule(serde_json::json!([
+            { "step_type": "regex_replace", "parameters": { "pattern": "^Gi(\\d+)$", "replacement": "ge-0/0/$1" } },
+            { "step_type": "regex_extract", "parameters": { "pattern": "^(ge-\\d+/\\d+/\\d+)" } }
+        ]));
+        TransformationEngine::compile_rule_patterns(&rule).unwrap();
+        let compiled = cached_regex("^Gi(\\d+)$").unwrap();
+
+        assert_eq!(engine.apply_transformation(&rule, "Gi7", None).unwrap(), "ge-0/0/7");
+        assert_eq!(engine.apply_transformation(&rule, "Gi8", None).unwrap(), "ge-0/0/8");
+        assert!(Arc::ptr_eq(&compiled, &cached_regex("^Gi(\\d+)$").unwrap()));
+
+        let bad_pattern = pipeline_rule(serde_json::json!([
+            { "step_type": "regex_extract", "parameters": { "pattern": "ge-(" } }
+        ]));
+        let error = TransformationEngine::compile_rule_patterns(&bad_pattern).unwrap_err();
+        assert!(error.contains("test_pipeline") && error.contains("ge-("), "{}", error);
+    }
+
+    #[test]
+    fn test_regex_extract_step_with_named_captures() {
+        let engine = TransformationEngine::new();
+        let host = pipeline_rule(serde_json::json!([
+            { "step_type": "regex_extract", "parameters": { "pattern": "^([^.]+)\\." } }
+        ]));
+        assert_eq!(engine.apply_transformation(&host, "srv-01.dc1.example.com", None).unwrap(), "srv-01");
+        assert_eq!(engine.apply_transformation(&host, "srv-02", None).unwrap(), "srv-02");
+
+        // Captures fill the step's own template and the placeholders of later steps
+        let mut context = HashMap::new();
+        context.insert("switch_label".to_string(), "leaf-01".to_string());
+        let label = pipeline_rule(serde_json::json!([
+            { "step_type": "regex_extract", "parameters": {
+                "pattern": "^(?P<host>[^.]+)\\.(?P<site>[^.]+)",
+                "template": "{site}-{host}",
+                "default": "unknown"
+            } },
+            { "step_type": "template", "parameters": { "template": "{input} on {switch_label} ({host})" } }
+        ]));
+        engine.validate_transformation_rule(&label).unwrap();
+        assert_eq!(engine.apply_transformation(&label, "srv-01.dc1.example.com", Some(&context)).unwrap(), "dc1-srv-01 on leaf-01 (srv-01)");
+        assert_eq!(engine.apply_transformation(&label, "srv-02", Some(&context)).unwrap(), "unknown on leaf-01 ()");
+
+        let invalid = pipeline_rule(serde_json::json!([
+            { "step_type": "regex_extract", "parameters": { "pattern": "(?P<host>" } }
+        ]));
+        assert!(engine.validate_transformation_rule(&invalid).is_err());
+        assert!(engine.apply_transformation(&invalid, "srv-01", None).is_err());
+    }
+
+    #[test]
+    fn test_list_step() {
+        let engine = TransformationEngine::new();
+        let rule = pipeline_rule(serde_json::json!([
+            { "step_type": "function", "parameters": { "name": "to_lowercase" } },
+            { "step_type": "list", "parameters": { "delimiters": [",", ";", "\n"], "sort": true, "join": "," } }
+        ]));
+        engine.validate_transformation_rule(&rule).unwrap();
+        assert_eq!(engine.apply_transformation(&rule, " Web;prod ,\nWeb,,rack-a ", None).unwrap(), "prod,rack-a,web");
+
+        let invalid = pipeline_rule(serde_json::json!([
+            { "step_type": "list", "parameters": { "sort": "yes" } }
+        ]));
+        assert!(engine.validate_transformation_rule(&invalid).is_err());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,203 @@
+use tauri::command;
+use crate::domains::shared::models::api_contracts::{NetworkConfigRow, ParsedSheetData, SheetParseOptions};
+use crate::models::enhanced_conversion_map::EnhancedConversionMap;
+use crate::domains::excel::services::worksheet_parser::{parse_worksheet_data_with_report, resolve_enhanced_conversion_map};
+use crate::utils::temp_file_manager::read_uploaded_file;
+use calamine::{Range, Data};
+use encoding_rs::{Encoding, WINDOWS_1252};
+use std::path::Path;
+
+/// Delimiters considered when sniffing a delimited text file
+const CANDIDATE_DELIMITERS: [u8; 4] = [b',', b'\t', b';', b'|'];
+
+/// Number of records sampled when sniffing the delimiter
+const DELIMITER_SAMPLE_RECORDS: usize = 20;
+
+#[command]
+pub async fn parse_csv_file(
+    file_id: String,
+    enhanced_conversion_map: Option<EnhancedConversionMap>,
+    header_row: Option<u32>,
+    delimiter: Option<String>,
+    encoding: Option<String>
+) -> Result<Vec<NetworkConfigRow>, String> {
+    let parsed_data = parse_uploaded_delimited_file(
+        &file_id, enhanced_conversion_map, header_row, delimiter, encoding, &SheetParseOptions::default()
+    )?;
+    Ok(parsed_data.rows)
+}
+
+#[command]
+pub async fn parse_csv_file_with_report(
+    file_id: String,
+    enhanced_conversion_map: Option<EnhancedConversionMap>,
+    header_row: Option<u32>,
+    delimiter: Option<String>,
+    encoding: Option<String>,
+    options: Option<SheetParseOptions>
+) -> Result<ParsedSheetData, String> {
+    parse_uploaded_delimited_file(
+        &file_id, enhanced_conversion_map, header_row, delimiter, encoding, &options.unwrap_or_default()
+    )
+}
+
+/// Parse an uploaded delimited text file, reporting skipped and degraded records like a worksheet
+fn parse_uploaded_delimited_file(
+    file_id: &str,
+    enhanced_conversion_map: Option<EnhancedConversionMap>,
+    header_row: Option<u32>,
+    delimiter: Option<String>,
+    encoding: Option<String>,
+    options: &SheetParseOptions
+) -> Result<ParsedSheetData, String> {
+    log::info!("Parsing delimited text from uploaded file {}", file_id);
+
+    // The verified content of the uploaded file is parsed without reopening it
+    let (entry, bytes) = read_uploaded_file(file_id)?;
+
+    // Exports usually carry their header on the first line, so allow overriding the map's header row
+    let mut effective_conversion_map = resolve_enhanced_conversion_map(enhanced_conversion_map)?;
+    if header_row.is_some() {
+        effective_conversion_map.header_row = header_row;
+    }
+
+    let text = decode_text(&bytes, encoding.as_deref())?;
+
+    let delimiter = match delimiter.as_deref() {
+        Some(delimiter) => parse_delimiter(delimiter)?,
+        None if has_extension(&entry.path, "tsv") => b'\t',
+        None => sniff_delimiter(&text),
+    };
+    log::info!("Using delimiter {:?}", delimiter as char);
+
+    let worksheet = read_delimited_records(&text, delimiter)?;
+    log::info!("Read {}x{} cells from delimited file", worksheet.get_size().0, worksheet.get_size().1);
+
+    // The uploaded file name stands in for the sheet name in row provenance
+    let sheet_name = entry.original_name;
+
+    // Delimited text has no merged cells, so pass empty merge metadata rather than none
+    // to keep empty header cells from being propagated as if they were merged
+    let parsed_data = parse_worksheet_data_with_report(&worksheet, &sheet_name, Some(&[]), &effective_conversion_map, options)?;
+
+    log::info!("Parsed {} rows of data ({} skipped, {} degraded)", 
+               parsed_data.report.parsed_rows, parsed_data.report.skipped_rows, parsed_data.report.degraded_rows);
+    Ok(parsed_data)
+}
+
+/// Decode file bytes into text
+///
+/// An explicit encoding label (e.g. "utf-16le", "shift_jis", "windows-1252") wins. Otherwise a
+/// byte order mark selects UTF-8/UTF-16, valid UTF-8 is used as-is, and anything else is
+/// decoded as Windows-1252, the usual encoding of spreadsheet exports on Windows.
+pub fn decode_text(bytes: &[u8], encoding_label: Option<&str>) -> Result<String, String> {
+    if let Some(label) = encoding_label {
+        let encoding = Encoding::for_label(label.trim().as_bytes())
+            .ok_or_else(|| format!("Unsupported text encoding: {}", label))?;
+        let (text, _, had_errors) = encoding.decode(bytes);
+        if had_errors {
+            log::warn!("File contains bytes that are invalid in {}, replaced with U+FFFD", encoding.name());
+        }
+        return Ok(text.into_owned());
+    }
+
+    if let Some((encoding, bom_length)) = Encoding::for_bom(bytes) {
+        log::debug!("Detected {} byte order mark", encoding.name());
+        return Ok(encoding.decode_without_bom_handling(&bytes[bom_length..]).0.into_owned());
+    }
+
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Ok(text.to_string()),
+        Err(_) => {
+            log::info!("File is not valid UTF-8, decoding as {}", WINDOWS_1252.name());
+            Ok(WINDOWS_1252.decode_without_bom_handling(bytes).0.into_owned())
+        }
+    }
+}
+
+/// Pick the delimiter that splits the sampled records into the most consistent column count
+///
+/// Records are split with the csv reader itself, so quoted delimiters and multi-line cells do
+/// not skew the counts. Ties favour the order of `CANDIDATE_DELIMITERS`; comma is the default.
+pub fn sniff_delimiter(text: &str) -> u8 {
+    let mut best_delimiter = b',';
+    let mut best_score = (0, 0);
+
+    for delimiter in CANDIDATE_DELIMITERS {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(text.as_bytes());
+
+        let field_counts: Vec<usize> = reader.records()
+            .filter_map(|record| record.ok())
+            .filter(|record| record.iter().any(|field| !field.trim().is_empty()))
+            .take(DELIMITER_SAMPLE_RECORDS)
+            .map(|record| record.len())
+            .collect();
+
+        let Some(&widest) = field_counts.iter().max() else {
+            continue;
+        };
+        if widest < 2 {
+            continue;
+        }
+
+        // Score by how many records share the widest column count, then by that count
+        let consistent_records = field_counts.iter().filter(|&&count| count == widest).count();
+        let score = (consistent_records, widest);
+        if score > best_score {
+            best_score = score;
+            best_delimiter = delimiter;
+        }
+    }
+
+    best_delimiter
+}
+
+/// Read delimited records into a string cell range so the Excel parsing pipeline can be reused
+pub fn read_delimited_records(text: &str, delimiter: u8) -> Result<Range<Data>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+
+    let mut records = Vec::new();
+    for (record_idx, record) in reader.records().enumerate() {
+        let record = record.map_err(|e| format!("Failed to read record {}: {}", record_idx + 1, e))?;
+        records.push(record.iter().map(|field| field.to_string()).collect::<Vec<String>>());
+    }
+
+    let column_count = records.iter().map(|record| record.len()).max().unwrap_or(0);
+    if records.is_empty() || column_count == 0 {
+        return Ok(Range::empty());
+    }
+
+    let mut worksheet = Range::new((0, 0), (records.len() as u32 - 1, column_count as u32 - 1));
+    for (row_idx, record) in records.into_iter().enumerate() {
+        for (col_idx, field) in record.into_iter().enumerate() {
+            if !field.is_empty() {
+                worksheet.set_value((row_idx as u32, col_idx as u32), Data::String(field));
+            }
+        }
+    }
+
+    Ok(worksheet)
+}
+
+fn parse_delimiter(delimiter: &str) -> Result<u8, String> {
+    match delimiter {
+        "\\t" | "tab" => Ok(b'\t'),
+        _ if delimiter.len() == 1 && delimiter.is_ascii() => Ok(delimiter.as_bytes()[0]),
+        _ => Err(format!("Delimiter must be a single ASCII character, got: {:?}", delimiter)),
+    }
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.eq_ignore_ascii_case(extension))
+        .unwrap_or(false)
+}
@@ -1,31 +1,56 @@
 use tauri::command;
-use calamine::{Reader, Xlsx, open_workbook};
 use std::path::Path;
+use crate::utils::file_utils::{FileUtils, SUPPORTED_SPREADSHEET_EXTENSIONS, DELIMITED_TEXT_EXTENSIONS};
+use crate::utils::temp_file_manager::TempFileManager;
+use crate::domains::excel::services::ExcelProcessingService;
+use crate::domains::shared::models::api_contracts::UploadedFile;
 
 #[command]
-pub async fn upload_excel_file(file_path: String) -> Result<Vec<String>, String> {
+pub async fn upload_excel_file(file_path: String) -> Result<UploadedFile, String> {
     log::info!("Processing Excel file: {}", file_path);
     
-    // Verify file exists and has .xlsx extension
+    // Verify file exists and has a supported spreadsheet or delimited text extension
     let path = Path::new(&file_path);
     if !path.exists() {
         return Err(format!("File does not exist: {}", file_path));
     }
     
-    if !file_path.to_lowercase().ends_with(".xlsx") {
-        return Err("File must be an Excel (.xlsx) file".to_string());
+    let is_delimited_text = FileUtils::is_delimited_text(&file_path);
+    if !FileUtils::validate_file_type(&file_path) && !is_delimited_text {
+        return Err(format!("File must be a spreadsheet with one of these extensions: .{}, .{}", 
+                           SUPPORTED_SPREADSHEET_EXTENSIONS.join(", ."), DELIMITED_TEXT_EXTENSIONS.join(", .")));
     }
     
-    // Open and read the Excel file
-    match open_workbook::<Xlsx<_>, _>(&file_path) {
-        Ok(workbook) => {
-            let sheet_names: Vec<String> = workbook.sheet_names().iter().map(|s| s.to_string()).collect();
+    // Copy the upload into the private temp directory; later commands refer to its file id
+    let temp_files = TempFileManager::global();
+    let entry = temp_files.register(&file_path)?;
+    
+    // Delimited text has a single "sheet", named after the file as in parse_csv_file
+    if is_delimited_text {
+        return Ok(UploadedFile {
+            sheet_names: vec![entry.original_name.clone()],
+            file_id: entry.file_id,
+            file_name: entry.original_name,
+            size: entry.size,
+            sha256: entry.sha256,
+        });
+    }
+    
+    // Open and read the workbook, detecting .xlsx/.xls/.xlsb/.ods from the file
+    match ExcelProcessingService::new().get_sheet_names(&entry.file_id) {
+        Ok(sheet_names) => {
             log::info!("Found {} sheets: {:?}", sheet_names.len(), sheet_names);
-            Ok(sheet_names)
+            Ok(UploadedFile {
+                file_id: entry.file_id,
+                file_name: entry.original_name,
+                size: entry.size,
+                sha256: entry.sha256,
+                sheet_names,
+            })
         }
-        Err(e) => {
-            let error_msg = format!("Failed to open Excel file: {}", e);
+        Err(error_msg) => {
             log::error!("{}", error_msg);
+            temp_files.remove(&entry.file_id)?;
             Err(error_msg)
         }
     }
@@ -34,7 +59,9 @@ pub async fn upload_excel_file(file_path: String) -> Result<Vec<String>, String>
 #[command]
 pub async fn cleanup_temp_file(file_id: String) -> Result<(), String> {
     log::info!("Cleaning up temporary file: {}", file_id);
-    // TODO: Implement temporary file cleanup based on file_id
-    // For now, just log the cleanup request
+    // Cleanup is idempotent, the file may already have been removed
+    if !TempFileManager::global().remove(&file_id)? {
+        log::debug!("No temporary file registered for {}", file_id);
+    }
     Ok(())
 }
\ No newline at end of file
@@ -1,5 +1,7 @@
 pub mod file_handler;
 pub mod data_parser;
+pub mod csv_parser;
 
 pub use file_handler::*;
-pub use data_parser::*;
\ No newline at end of file
+pub use data_parser::*;
+pub use csv_parser::*;
\ No newline at end of file
@@ -1,4 +1,16 @@
-use crate::domains::shared::models::api_contracts::NetworkConfigRow;
+use crate::domains::shared::models::api_contracts::{
+    NetworkConfigRow, ParsedSheetData, ParsedWorkbookData, ParseReport, SheetParseReport, SheetParseOptions, SheetPreview,
+    ExcelTableInfo, WorkbookDiff, column_letter
+};
+use crate::models::enhanced_conversion_map::{EnhancedConversionMap, HeaderRowDetection};
+use super::workbook_diff::diff_rows;
+use super::worksheet_parser::{
+    parse_worksheet_data_with_report, parse_table_data_with_report, detect_worksheet_header_row,
+    find_cross_sheet_duplicates, build_sheet_preview
+};
+use crate::utils::temp_file_manager::read_uploaded_file;
+use calamine::{Reader, Sheets, Xlsx, open_workbook_auto_from_rs, Range, Data, Dimensions};
+use std::io::{Cursor, Read, Seek};
 
 pub struct ExcelProcessingService;
 
@@ -13,13 +25,246 @@ impl ExcelProcessingService {
         Self
     }
 
-    pub fn get_sheet_names(&self, _file_path: &str) -> Result<Vec<String>, String> {
-        // TODO: Implement Excel sheet enumeration
-        Ok(vec!["Sheet1".to_string()])
+    pub fn get_sheet_names(&self, file_id: &str) -> Result<Vec<String>, String> {
+        let workbook = open_workbook(file_id)?;
+        Ok(workbook.sheet_names())
     }
 
-    pub fn parse_sheet(&self, _file_path: &str, _sheet_name: &str) -> Result<Vec<NetworkConfigRow>, String> {
-        // TODO: Implement Excel sheet parsing
-        Ok(vec![])
+    /// Read a worksheet together with its merged region metadata (None when it cannot be loaded)
+    /// 
+    /// The workbook format (.xlsx, .xls, .xlsb, .ods) is detected from the file. Merge metadata
+    /// is available for .xlsx and .xls; other formats only get fill-down columns filled.
+    pub fn read_worksheet(&self, file_id: &str, sheet_name: &str) -> Result<(Range<Data>, Option<Vec<Dimensions>>), String> {
+        let mut workbook = open_workbook(file_id)?;
+        read_worksheet(&mut workbook, sheet_name)
     }
-}
\ No newline at end of file
+
+    pub fn parse_sheet(
+        &self,
+        file_id: &str,
+        sheet_name: &str,
+        enhanced_conversion_map: &EnhancedConversionMap,
+        options: &SheetParseOptions
+    ) -> Result<Vec<NetworkConfigRow>, String> {
+        self.parse_sheet_with_report(file_id, sheet_name, enhanced_conversion_map, options)
+            .map(|parsed_sheet| parsed_sheet.rows)
+    }
+
+    pub fn parse_sheet_with_report(
+        &self,
+        file_id: &str,
+        sheet_name: &str,
+        enhanced_conversion_map: &EnhancedConversionMap,
+        options: &SheetParseOptions
+    ) -> Result<ParsedSheetData, String> {
+        let (worksheet, merged_regions) = self.read_worksheet(file_id, sheet_name)?;
+        log::info!("Sheet dimensions: {}x{}", worksheet.get_size().0, worksheet.get_size().1);
+        
+        parse_worksheet_data_with_report(&worksheet, sheet_name, merged_regions.as_deref(), enhanced_conversion_map, options)
+    }
+
+    /// Parse several sheets (all sheets when none are given) with one conversion map into a combined dataset
+    /// 
+    /// Rows keep their origin sheet in `source`; rows sharing switch_label + switch_ifname across
+    /// sheets are all kept and reported as duplicates.
+    pub fn parse_sheets(
+        &self,
+        file_id: &str,
+        sheet_names: Option<&[String]>,
+        enhanced_conversion_map: &EnhancedConversionMap,
+        options: &SheetParseOptions
+    ) -> Result<ParsedWorkbookData, String> {
+        let mut workbook = open_workbook(file_id)?;
+        
+        let sheet_names = match sheet_names {
+            Some([]) => return Err("No sheets selected".to_string()),
+            Some(sheet_names) => sheet_names.to_vec(),
+            None => workbook.sheet_names(),
+        };
+        
+        let mut rows = Vec::new();
+        let mut sheet_reports = Vec::new();
+        for sheet_name in &sheet_names {
+            let (worksheet, merged_regions) = read_worksheet(&mut workbook, sheet_name)?;
+            let parsed_sheet = parse_worksheet_data_with_report(&worksheet, sheet_name, merged_regions.as_deref(), enhanced_conversion_map, options)?;
+            
+            log::info!("Parsed {} rows from sheet '{}'", parsed_sheet.rows.len(), sheet_name);
+            rows.extend(parsed_sheet.rows);
+            sheet_reports.push(SheetParseReport {
+                sheet_name: sheet_name.clone(),
+                report: parsed_sheet.report,
+            });
+        }
+        
+        let duplicates = find_cross_sheet_duplicates(&rows);
+        if !duplicates.is_empty() {
+            log::warn!("Found {} switch interfaces used on more than one sheet", duplicates.len());
+        }
+        
+        log::info!("Parsed {} rows from {} sheets", rows.len(), sheet_names.len());
+        Ok(ParsedWorkbookData { rows, sheet_reports, duplicates })
+    }
+
+    /// Parse two versions of a workbook with the same map and compare their rows
+    /// 
+    /// Rows are keyed by switch_label + switch_ifname; modified rows list their changed fields.
+    pub fn diff_workbooks(
+        &self,
+        old_file_id: &str,
+        new_file_id: &str,
+        sheet_names: Option<&[String]>,
+        enhanced_conversion_map: &EnhancedConversionMap,
+        options: &SheetParseOptions
+    ) -> Result<WorkbookDiff, String> {
+        let old_workbook = self.parse_sheets(old_file_id, sheet_names, enhanced_conversion_map, options)?;
+        let new_workbook = self.parse_sheets(new_file_id, sheet_names, enhanced_conversion_map, options)?;
+        
+        let diff = diff_rows(&old_workbook.rows, &new_workbook.rows);
+        log::info!("Workbook diff: {} added, {} removed, {} modified, {} unchanged",
+                   diff.added.len(), diff.removed.len(), diff.modified.len(), diff.unchanged_count);
+        Ok(diff)
+    }
+
+    /// Named tables (ListObjects) of an .xlsx workbook, in sheet order
+    pub fn list_tables(&self, file_id: &str) -> Result<Vec<ExcelTableInfo>, String> {
+        let mut workbook = open_workbook(file_id)?;
+        let sheet_names = workbook.sheet_names();
+        let xlsx = load_tables(&mut workbook)?;
+        
+        let table_names: Vec<String> = sheet_names.iter()
+            .flat_map(|sheet_name| xlsx.table_names_in_sheet(sheet_name))
+            .cloned()
+            .collect();
+        table_names.iter()
+            .map(|table_name| read_table(xlsx, table_name).map(|(table, _)| table))
+            .collect()
+    }
+
+    /// Parse the body of a named table, using its column names as headers
+    /// 
+    /// The table's bounds replace header row detection; rows keep their sheet position in `source`.
+    pub fn parse_table_with_report(
+        &self,
+        file_id: &str,
+        table_name: &str,
+        enhanced_conversion_map: &EnhancedConversionMap,
+        options: &SheetParseOptions
+    ) -> Result<ParsedSheetData, String> {
+        let mut workbook = open_workbook(file_id)?;
+        let (table, table_data) = read_table(load_tables(&mut workbook)?, table_name)?;
+        let Some(table_data) = table_data else {
+            log::info!("Table '{}' has no data rows", table_name);
+            return Ok(ParsedSheetData { rows: Vec::new(), report: ParseReport::default() });
+        };
+        log::info!("Table '{}' on sheet '{}' spans {} with columns {:?}", table.name, table.sheet_name, table.range, table.columns);
+        
+        // Read the whole sheet so blueprint cells outside the table can still be resolved
+        let (worksheet, merged_regions) = read_worksheet(&mut workbook, &table.sheet_name)?;
+        parse_table_data_with_report(
+            &worksheet,
+            &table.sheet_name,
+            merged_regions.as_deref(),
+            &table.columns,
+            table_data,
+            enhanced_conversion_map,
+            options
+        )
+    }
+
+    pub fn detect_header_row(
+        &self,
+        file_id: &str,
+        sheet_name: &str,
+        enhanced_conversion_map: &EnhancedConversionMap,
+        max_rows: u32
+    ) -> Result<Option<HeaderRowDetection>, String> {
+        let (worksheet, merged_regions) = self.read_worksheet(file_id, sheet_name)?;
+        detect_worksheet_header_row(&worksheet, merged_regions.as_deref(), enhanced_conversion_map, max_rows)
+    }
+
+    /// Raw preview of the first `max_rows` rows as typed cells with their merge spans
+    pub fn preview_sheet(&self, file_id: &str, sheet_name: &str, max_rows: usize) -> Result<SheetPreview, String> {
+        let (worksheet, merged_regions) = self.read_worksheet(file_id, sheet_name)?;
+        Ok(build_sheet_preview(&worksheet, sheet_name, merged_regions.as_deref(), max_rows))
+    }
+}
+
+/// Open an uploaded workbook by file id from its verified content
+fn open_workbook(file_id: &str) -> Result<Sheets<Cursor<Vec<u8>>>, String> {
+    let (_, content) = read_uploaded_file(file_id)?;
+    open_workbook_auto_from_rs(Cursor::new(content))
+        .map_err(|e| format!("Failed to open Excel file: {}", e))
+}
+
+/// Read a worksheet and its merged region metadata from an already opened workbook
+fn read_worksheet<RS: Read + Seek>(workbook: &mut Sheets<RS>, sheet_name: &str) -> Result<(Range<Data>, Option<Vec<Dimensions>>), String> {
+    // Get the specific worksheet
+    let worksheet = workbook.worksheet_range(sheet_name)
+        .map_err(|e| format!("Failed to read sheet '{}': {}", sheet_name, e))?;
+    
+    log::debug!("Worksheet dimensions: {:?}", worksheet.get_size());
+    
+    // Load merged region metadata so merged cells can be expanded
+    // from the real merge ranges instead of guessed from neighbouring values
+    let merged_regions: Option<Vec<Dimensions>> = match workbook {
+        Sheets::Xlsx(xlsx) => match xlsx.load_merged_regions() {
+            Ok(()) => Some(xlsx.merged_regions_by_sheet(sheet_name)
+                .into_iter()
+                .map(|(_, _, dimensions)| *dimensions)
+                .collect()),
+            Err(e) => {
+                log::warn!("Could not load merged regions, only fill-down columns will be filled: {}", e);
+                None
+            }
+        },
+        Sheets::Xls(xls) => xls.worksheet_merge_cells(sheet_name),
+        Sheets::Xlsb(_) | Sheets::Ods(_) => {
+            log::info!("Merged region metadata is not available for this workbook format, only fill-down columns will be filled");
+            None
+        }
+    };
+    
+    if let Some(regions) = &merged_regions {
+        log::info!("Found {} merged regions in sheet '{}': {:?}", 
+                   regions.len(), sheet_name, regions);
+    }
+    
+    Ok((worksheet, merged_regions))
+}
+
+/// Load the table definitions of an .xlsx workbook; other formats have no named tables
+fn load_tables<RS: Read + Seek>(workbook: &mut Sheets<RS>) -> Result<&mut Xlsx<RS>, String> {
+    match workbook {
+        Sheets::Xlsx(xlsx) => {
+            xlsx.load_tables()
+                .map_err(|e| format!("Failed to read table definitions: {}", e))?;
+            Ok(xlsx)
+        }
+        Sheets::Xls(_) | Sheets::Xlsb(_) | Sheets::Ods(_) => {
+            Err("Named tables are only supported in .xlsx workbooks".to_string())
+        }
+    }
+}
+
+/// Read a table's metadata and the bounds of its body (None when the table has no data rows)
+fn read_table<RS: Read + Seek>(xlsx: &mut Xlsx<RS>, table_name: &str) -> Result<(ExcelTableInfo, Option<Dimensions>), String> {
+    let table = xlsx.table_by_name(table_name)
+        .map_err(|e| format!("Failed to read table '{}': {}", table_name, e))?;
+    let table_data = table.data().start()
+        .zip(table.data().end())
+        .map(|(start, end)| Dimensions::new(start, end));
+    let range = table_data
+        .map(|bounds| format!("{}{}:{}{}",
+            column_letter(bounds.start.1), bounds.start.0 + 1,
+            column_letter(bounds.end.1), bounds.end.0 + 1))
+        .unwrap_or_default();
+    
+    let table_info = ExcelTableInfo {
+        name: table.name().to_string(),
+        sheet_name: table.sheet_name().to_string(),
+        columns: table.columns().to_vec(),
+        range,
+        row_count: table.data().height(),
+    };
+    Ok((table_info, table_data))
+}
@@ -1,3 +1,8 @@
 pub mod excel_processing_service;
+pub mod worksheet_parser;
+pub mod row_filter;
+pub mod port_range;
+pub mod workbook_diff;
 
-pub use excel_processing_service::ExcelProcessingService;
\ No newline at end of file
+pub use excel_processing_service::ExcelProcessingService;
+pub use worksheet_parser::*;
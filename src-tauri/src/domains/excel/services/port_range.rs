@@ -0,0 +1,77 @@
+use regex::Regex;
+use std::collections::HashMap;
+
+/// Largest number of members a single interface range may expand to
+const MAX_RANGE_MEMBERS: u32 = 256;
+
+/// Expand an interface range into its members, e.g. "et-0/0/[10-13]" or "eth[0-3]"
+/// 
+/// The range is a number, a dash and a larger number in brackets at the end of the name, so plain
+/// names such as "eth1-2" are never split. Leading zeros of the first number are kept ("eth[01-03]"
+/// gives "eth01", "eth02", "eth03"). Any other value is returned as its only member.
+pub fn expand_port_range(value: &str) -> Result<Vec<String>, String> {
+    lazy_static::lazy_static! {
+        static ref RANGE_REGEX: Regex = Regex::new(r"^(.*?)\[(\d+)-(\d+)\]$").unwrap();
+    }
+
+    let value = value.trim();
+    let Some(captures) = RANGE_REGEX.captures(value) else {
+        return Ok(vec![value.to_string()]);
+    };
+    let prefix = &captures[1];
+    let (first, last) = (&captures[2], &captures[3]);
+    let (Ok(first_number), Ok(last_number)) = (first.parse::<u32>(), last.parse::<u32>()) else {
+        return Err(format!("interface range '{}' is out of bounds", value));
+    };
+    if first_number >= last_number {
+        return Err(format!("interface range '{}' must count upwards", value));
+    }
+    if last_number - first_number + 1 > MAX_RANGE_MEMBERS {
+        return Err(format!("interface range '{}' has more than {} members", value, MAX_RANGE_MEMBERS));
+    }
+
+    let width = if first.starts_with('0') { first.len() } else { 0 };
+    Ok((first_number..=last_number)
+        .map(|number| format!("{}{:0width$}", prefix, number, width = width))
+        .collect())
+}
+
+/// Expand the range fields of a row into one row per member, pairing members positionally
+/// 
+/// Empty fields are left as they are. When the non-empty fields expand to different member
+/// counts, the error lists each field with its count.
+pub fn expand_row_port_ranges(
+    field_data: &HashMap<String, String>,
+    range_fields: &[String]
+) -> Result<Vec<HashMap<String, String>>, String> {
+    let mut expanded_fields = Vec::new();
+    for field_name in range_fields {
+        let Some(value) = field_data.get(field_name).filter(|value| !value.trim().is_empty()) else {
+            continue;
+        };
+        let members = expand_port_range(value)
+            .map_err(|e| format!("{}: {}", field_name, e))?;
+        expanded_fields.push((field_name, members));
+    }
+
+    let member_count = expanded_fields.iter().map(|(_, members)| members.len()).max().unwrap_or(1);
+    if member_count == 1 {
+        return Ok(vec![field_data.clone()]);
+    }
+    if expanded_fields.iter().any(|(_, members)| members.len() != member_count) {
+        let counts: Vec<String> = expanded_fields.iter()
+            .map(|(field_name, members)| format!("{} has {}", field_name, members.len()))
+            .collect();
+        return Err(format!("interface ranges have different member counts: {}", counts.join(", ")));
+    }
+
+    Ok((0..member_count)
+        .map(|member_idx| {
+            let mut member_data = field_data.clone();
+            for (field_name, members) in &expanded_fields {
+                member_data.insert(field_name.to_string(), members[member_idx].clone());
+            }
+            member_data
+        })
+        .collect())
+}
@@ -0,0 +1,134 @@
+use regex::Regex;
+use std::collections::HashMap;
+
+/// A predicate on a row's mapped field values, e.g. `switch_label ~ "^leaf-a"` or `comment != "DECOM"`
+/// 
+/// Syntax is `<field> <operator> <value>` with the operators `==` (or `=`), `!=`, `~` (regex match)
+/// and `!~` (regex does not match). The value may be double-quoted; `\"` and `\\` escape inside quotes.
+/// Values are compared after trimming, and a field missing from the row compares as empty.
+#[derive(Debug, Clone)]
+pub struct RowPredicate {
+    pub field: String,
+    pub operator: PredicateOperator,
+    pub value: String,
+    regex: Option<Regex>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PredicateOperator {
+    Equals,
+    NotEquals,
+    Matches,
+    NotMatches,
+}
+
+impl RowPredicate {
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let expression = expression.trim();
+        let field_end = expression.find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(expression.len());
+        let field = &expression[..field_end];
+        if field.is_empty() {
+            return Err(format!("Row filter '{}' must start with a field name", expression));
+        }
+
+        let rest = expression[field_end..].trim_start();
+        let (operator, rest) = [
+            ("==", PredicateOperator::Equals),
+            ("!=", PredicateOperator::NotEquals),
+            ("!~", PredicateOperator::NotMatches),
+            ("=", PredicateOperator::Equals),
+            ("~", PredicateOperator::Matches),
+        ]
+            .into_iter()
+            .find_map(|(token, operator)| rest.strip_prefix(token).map(|rest| (operator, rest)))
+            .ok_or_else(|| format!("Row filter '{}' needs one of the operators ==, !=, ~, !~", expression))?;
+
+        let value = parse_predicate_value(rest.trim())
+            .map_err(|e| format!("Row filter '{}': {}", expression, e))?;
+
+        let regex = match operator {
+            PredicateOperator::Matches | PredicateOperator::NotMatches => Some(
+                Regex::new(&value).map_err(|e| format!("Row filter '{}' has an invalid regex: {}", expression, e))?
+            ),
+            PredicateOperator::Equals | PredicateOperator::NotEquals => None,
+        };
+
+        Ok(Self {
+            field: field.to_string(),
+            operator,
+            value,
+            regex,
+        })
+    }
+
+    pub fn matches(&self, field_data: &HashMap<String, String>) -> bool {
+        let field_value = field_data.get(&self.field).map(|value| value.trim()).unwrap_or("");
+        match (&self.operator, &self.regex) {
+            (PredicateOperator::Equals, _) => field_value == self.value,
+            (PredicateOperator::NotEquals, _) => field_value != self.value,
+            (PredicateOperator::Matches, Some(regex)) => regex.is_match(field_value),
+            (PredicateOperator::NotMatches, Some(regex)) => !regex.is_match(field_value),
+            (PredicateOperator::Matches | PredicateOperator::NotMatches, None) => false,
+        }
+    }
+}
+
+/// Include and exclude predicates applied to every converted row
+/// 
+/// A row is kept when it matches every include predicate and none of the exclude predicates.
+#[derive(Debug, Clone, Default)]
+pub struct RowFilter {
+    include: Vec<RowPredicate>,
+    exclude: Vec<RowPredicate>,
+}
+
+impl RowFilter {
+    pub fn parse(include: &[String], exclude: &[String]) -> Result<Self, String> {
+        Ok(Self {
+            include: include.iter().map(|expression| RowPredicate::parse(expression)).collect::<Result<_, _>>()?,
+            exclude: exclude.iter().map(|expression| RowPredicate::parse(expression)).collect::<Result<_, _>>()?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    pub fn accepts(&self, field_data: &HashMap<String, String>) -> bool {
+        self.include.iter().all(|predicate| predicate.matches(field_data))
+            && !self.exclude.iter().any(|predicate| predicate.matches(field_data))
+    }
+}
+
+fn parse_predicate_value(raw_value: &str) -> Result<String, String> {
+    let Some(quoted) = raw_value.strip_prefix('"') else {
+        return Ok(raw_value.to_string());
+    };
+
+    let mut value = String::new();
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(escaped @ ('"' | '\\')) => value.push(escaped),
+                Some(other) => {
+                    // Keep other escapes (e.g. regex classes like \d) as written
+                    value.push('\\');
+                    value.push(other);
+                }
+                None => return Err("unterminated escape".to_string()),
+            },
+            '"' => {
+                return if chars.as_str().trim().is_empty() {
+                    Ok(value)
+                } else {
+                    Err("unexpected text after the closing quote".to_string())
+                };
+            }
+            _ => value.push(c),
+        }
+    }
+
+    Err("missing closing quote".to_string())
+}
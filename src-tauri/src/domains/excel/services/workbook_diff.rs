@@ -0,0 +1,97 @@
+use crate::domains::shared::models::api_contracts::{FieldChange, ModifiedRow, NetworkConfigRow, WorkbookDiff};
+use std::collections::{HashMap, VecDeque};
+
+/// Compare the rows of two workbook versions, keyed by switch_label + switch_ifname
+///
+/// Rows whose key appears in both versions are compared field by field; provenance (`source`)
+/// is not compared. A key that occurs more than once in a version (the same interface on two
+/// sheets) is paired with the other version's rows in order of occurrence.
+pub fn diff_rows(old_rows: &[NetworkConfigRow], new_rows: &[NetworkConfigRow]) -> WorkbookDiff {
+    let mut old_by_key: HashMap<(String, String), VecDeque<usize>> = HashMap::new();
+    for (row_idx, row) in old_rows.iter().enumerate() {
+        if let Some(key) = row_key(row) {
+            old_by_key.entry(key).or_default().push_back(row_idx);
+        }
+    }
+
+    let mut diff = WorkbookDiff::default();
+    for row in new_rows {
+        let Some(key) = row_key(row) else {
+            continue;
+        };
+        let Some(old_idx) = old_by_key.get_mut(&key).and_then(|row_indices| row_indices.pop_front()) else {
+            diff.added.push(row.clone());
+            continue;
+        };
+        let old_row = &old_rows[old_idx];
+
+        let changes = field_changes(old_row, row);
+        if changes.is_empty() {
+            diff.unchanged_count += 1;
+        } else {
+            diff.modified.push(ModifiedRow {
+                switch_label: key.0,
+                switch_ifname: key.1,
+                changes,
+                previous_source: old_row.source.clone(),
+                row: row.clone(),
+            });
+        }
+    }
+
+    // Whatever was not paired no longer exists, reported in the old workbook's order
+    let mut removed_indices: Vec<usize> = old_by_key.into_values().flatten().collect();
+    removed_indices.sort_unstable();
+    diff.removed = removed_indices.into_iter()
+        .map(|row_idx| old_rows[row_idx].clone())
+        .collect();
+
+    diff
+}
+
+fn row_key(row: &NetworkConfigRow) -> Option<(String, String)> {
+    let switch_label = row.switch_label.as_deref()?.trim();
+    let switch_ifname = row.switch_ifname.as_deref()?.trim();
+    if switch_label.is_empty() || switch_ifname.is_empty() {
+        return None;
+    }
+    Some((switch_label.to_string(), switch_ifname.to_string()))
+}
+
+fn field_changes(old_row: &NetworkConfigRow, new_row: &NetworkConfigRow) -> Vec<FieldChange> {
+    compared_fields(old_row).into_iter()
+        .zip(compared_fields(new_row))
+        .filter(|((_, old_value), (_, new_value))| old_value != new_value)
+        .map(|((field, old_value), (_, new_value))| FieldChange {
+            field: field.to_string(),
+            old_value,
+            new_value,
+        })
+        .collect()
+}
+
+/// Non-key field values of a row in a fixed order; switch_tags is left out since it mirrors link_group_tags
+/// 
+/// List fields compare as sorted items, so reordered tags or CTs are not reported as changes.
+fn compared_fields(row: &NetworkConfigRow) -> [(&'static str, Option<String>); 12] {
+    [
+        ("blueprint", row.blueprint.clone()),
+        ("server_label", row.server_label.clone()),
+        ("is_external", row.is_external.map(|is_external| is_external.to_string())),
+        ("server_tags", list_value(&row.server_tags)),
+        ("link_group_ifname", row.link_group_ifname.clone()),
+        ("link_group_lag_mode", row.link_group_lag_mode.clone()),
+        ("link_group_ct_names", list_value(&row.link_group_ct_names)),
+        ("link_group_tags", row.link_group_tags.clone()),
+        ("link_speed", row.link_speed.clone()),
+        ("server_ifname", row.server_ifname.clone()),
+        ("link_tags", list_value(&row.link_tags)),
+        ("comment", row.comment.clone()),
+    ]
+}
+
+fn list_value(items: &[String]) -> Option<String> {
+    let mut items = items.to_vec();
+    items.sort();
+    (!items.is_empty()).then(|| items.join(", "))
+}
@@ -0,0 +1,1544 @@
+use crate::domains::shared::models::api_contracts::{
+    NetworkConfigRow, ParsedSheetData, ParseReport, RowParseIssue, RowParseStatus, ProcessingError, ErrorType,
+    RowSource, column_letter, parse_cell_reference, parse_cell_range, SheetParseOptions, DuplicateRowGroup, SheetPreview, PreviewCell, PreviewCellType, MergeSpan
+};
+use crate::models::enhanced_conversion_map::{
+    EnhancedConversionMap, MergeBehavior, HeaderDetectionMode, HeaderRowDetection, BlueprintSource,
+    CellCoercion, NumberCoercion, DateCoercion, ArrayFormat
+};
+use crate::domains::conversion::services::enhanced_conversion_service::EnhancedConversionService;
+use super::row_filter::RowFilter;
+use super::port_range::expand_row_port_ranges;
+use calamine::{Range, Data, DataType, Dimensions};
+use chrono::Datelike;
+use std::collections::HashMap;
+
+/// Error values Excel shows in cells, also found as plain text in CSV exports
+const EXCEL_ERROR_LITERALS: [&str; 7] = ["#N/A", "#REF!", "#VALUE!", "#DIV/0!", "#NAME?", "#NULL!", "#NUM!"];
+
+/// Largest distance from a whole number still treated as floating point noise
+const INTEGER_TOLERANCE: f64 = 1e-9;
+
+/// Use the provided enhanced conversion map or load the default one
+pub(crate) fn resolve_enhanced_conversion_map(enhanced_conversion_map: Option<EnhancedConversionMap>) -> Result<EnhancedConversionMap, String> {
+    if let Some(map) = enhanced_conversion_map {
+        log::info!("Using provided enhanced conversion map with {} field definitions", map.field_definitions.len());
+        return Ok(map);
+    }
+    
+    match EnhancedConversionService::load_default_enhanced_conversion_map() {
+        Ok(default_map) => {
+            log::info!("Using default enhanced conversion map with {} field definitions, header_row: {:?}", 
+                default_map.field_definitions.len(), default_map.header_row);
+            log::debug!("Default field definitions: {:?}", default_map.field_definitions.keys().collect::<Vec<_>>());
+            Ok(default_map)
+        }
+        Err(e) => Err(format!("Failed to load enhanced conversion map: {}", e)),
+    }
+}
+
+/// Parse worksheet rows and report every source row that was skipped or only partially converted
+/// 
+/// Each parsed row records its sheet name, sheet row and the column every field was read from.
+/// `options` can limit parsing to a cell range and filter rows on their converted field values.
+pub(crate) fn parse_worksheet_data_with_report(
+    worksheet: &Range<Data>, 
+    sheet_name: &str,
+    merged_regions: Option<&[Dimensions]>,
+    enhanced_conversion_map: &EnhancedConversionMap,
+    options: &SheetParseOptions
+) -> Result<ParsedSheetData, String> {
+    parse_rows_with_report(worksheet, sheet_name, merged_regions, enhanced_conversion_map, options, None)
+}
+
+/// Parse the body of an Excel table (ListObject) on a worksheet
+/// 
+/// `table_data` are the bounds of the table body below its header row and `table_columns` the
+/// table's column names, so no header row is detected and every body row is read as data.
+pub(crate) fn parse_table_data_with_report(
+    worksheet: &Range<Data>,
+    sheet_name: &str,
+    merged_regions: Option<&[Dimensions]>,
+    table_columns: &[String],
+    table_data: Dimensions,
+    enhanced_conversion_map: &EnhancedConversionMap,
+    options: &SheetParseOptions
+) -> Result<ParsedSheetData, String> {
+    if options.range.is_some() {
+        return Err("A cell range cannot be combined with a table, the table bounds are used".to_string());
+    }
+    parse_rows_with_report(worksheet, sheet_name, merged_regions, enhanced_conversion_map, options, Some((table_columns, table_data)))
+}
+
+fn parse_rows_with_report(
+    worksheet: &Range<Data>, 
+    sheet_name: &str,
+    merged_regions: Option<&[Dimensions]>,
+    enhanced_conversion_map: &EnhancedConversionMap,
+    options: &SheetParseOptions,
+    table: Option<(&[String], Dimensions)>
+) -> Result<ParsedSheetData, String> {
+    let mut rows = Vec::new();
+    let mut report = ParseReport::default();
+    let row_filter = RowFilter::parse(&options.include, &options.exclude)?;
+    
+    // Expand merged regions from Excel metadata before reading any rows, so that
+    // vertical and horizontal merges in every column (headers included) carry their value
+    let expanded_worksheet = merged_regions.map(|regions| expand_merged_regions(worksheet, regions));
+    let source_worksheet = expanded_worksheet.as_ref().unwrap_or(worksheet);
+    
+    // Blueprint cells may lie outside the selected range, so resolve them against the whole sheet
+    let blueprint_candidates = resolve_blueprint_candidates(source_worksheet, sheet_name, enhanced_conversion_map)?;
+    
+    // A selected range cuts the sheet to its columns here; its rows limit the data rows below, so the
+    // header row is still found above the range and merged regions are filled from outside it
+    let selected_range = options.range.as_deref()
+        .map(|range| parse_cell_range(range)
+            .ok_or_else(|| format!("Invalid cell range '{}', expected a range like A5:N400", range)))
+        .transpose()?;
+    let selected_worksheets = match (selected_range, worksheet.start(), worksheet.end()) {
+        _ if table.is_some() => table.map(|(_, table_data)| (
+            worksheet.range(table_data.start, table_data.end),
+            source_worksheet.range(table_data.start, table_data.end),
+        )),
+        (Some(((_, first_col), (_, last_col))), Some((first_row, _)), Some((last_row, _))) => {
+            log::info!("Limiting sheet '{}' to range {}", sheet_name, options.range.as_deref().unwrap_or_default());
+            Some((
+                worksheet.range((first_row, first_col), (last_row, last_col)),
+                source_worksheet.range((first_row, first_col), (last_row, last_col)),
+            ))
+        }
+        _ => None,
+    };
+    let (worksheet, source_worksheet) = match &selected_worksheets {
+        Some((raw_worksheet, expanded_worksheet)) => (raw_worksheet, expanded_worksheet),
+        None => (worksheet, source_worksheet),
+    };
+    let selected_rows = selected_range.map(|((first_row, _), (last_row, _))| first_row as usize + 1..=last_row as usize + 1);
+    
+    // Get all rows from the worksheet, keeping the raw rows for columns that opt out of merge expansion
+    let worksheet_rows: Vec<Vec<Data>> = source_worksheet.rows().map(|row| row.to_vec()).collect();
+    let raw_worksheet_rows: Vec<Vec<Data>> = worksheet.rows().map(|row| row.to_vec()).collect();
+    
+    if worksheet_rows.is_empty() {
+        return Ok(ParsedSheetData { rows, report });
+    }
+    
+    // Rows and columns are reported by their sheet position, which is offset when the used range does not start at A1
+    let (first_sheet_row, first_sheet_col) = worksheet.start()
+        .map(|(row, col)| (row as usize, col))
+        .unwrap_or((0, 0));
+    
+    let (headers, data_start_idx) = match table {
+        Some((table_columns, _)) => {
+            // A table names its columns exactly and its body holds only data rows
+            report.header_row = (first_sheet_row > 0).then_some(first_sheet_row);
+            (table_columns.to_vec(), 0)
+        }
+        None => {
+            // Determine header row index from enhanced conversion map, detecting it when configured
+            let header_row_idx = resolve_header_row_index(&worksheet_rows, first_sheet_row, merged_regions.is_some(), enhanced_conversion_map)?;
+            
+            if header_row_idx >= worksheet_rows.len() {
+                log::warn!("Header row {} is beyond the {} rows of the sheet, no data parsed", 
+                           header_row_idx + 1, worksheet_rows.len());
+                return Ok(ParsedSheetData { rows, report });
+            }
+            report.header_row = Some(first_sheet_row + header_row_idx + 1);
+            log::info!("Header row index: {}", header_row_idx);
+            
+            // Multi-row headers span several rows starting at the header row; data starts below them
+            let header_row_span = header_row_span(enhanced_conversion_map);
+            let data_start_idx = (header_row_idx + header_row_span).min(worksheet_rows.len());
+            (extract_headers(&worksheet_rows[header_row_idx..data_start_idx], merged_regions.is_some()), data_start_idx)
+        }
+    };
+    
+    log::info!("Found headers (original case): {:?}", headers);
+    
+    // Add detailed debug logging for each header with byte representation
+    for (i, header) in headers.iter().enumerate() {
+        log::debug!("Header[{}]: '{}' (bytes: {:?})", i, header, header.as_bytes());
+    }
+    
+    // Create field mapping using enhanced conversion system
+    let service = EnhancedConversionService::new();
+    let conversion_result = service.convert_headers_with_enhanced_map(&headers, enhanced_conversion_map)
+        .map_err(|e| format!("Failed to convert headers: {}", e))?;
+    
+    log::info!("Header conversion successful: {} headers mapped", conversion_result.converted_headers.len());
+    log::debug!("Header mappings: {:?}", conversion_result.converted_headers);
+    
+    for header in &headers {
+        if !header.is_empty() 
+            && !conversion_result.converted_headers.contains_key(header) 
+            && !report.unmapped_headers.contains(header) {
+            report.unmapped_headers.push(header.clone());
+        }
+    }
+    if !report.unmapped_headers.is_empty() {
+        log::info!("Unmapped headers: {:?}", report.unmapped_headers);
+    }
+    
+    // Resolve each column's merge behavior from the field definition it maps to
+    let column_merge_behaviors: Vec<MergeBehavior> = headers.iter()
+        .map(|header| {
+            conversion_result.converted_headers.get(header)
+                .and_then(|field_name| enhanced_conversion_map.field_definitions.get(field_name))
+                .map(|field_def| field_def.merge_behavior.clone())
+                .unwrap_or_default()
+        })
+        .collect();
+    
+    log::debug!("Column merge behaviors: {:?}", headers.iter().zip(&column_merge_behaviors).collect::<Vec<_>>());
+    
+    // Source column letter of each header, used for cell provenance (first occurrence wins)
+    let mut header_columns: HashMap<&str, String> = HashMap::new();
+    for (col_idx, header) in headers.iter().enumerate() {
+        header_columns.entry(header.as_str())
+            .or_insert_with(|| column_letter(first_sheet_col + col_idx as u32));
+    }
+    
+    // Fill-down starts at the first row of a selected range, so only real merged regions carry
+    // values into the range from rows above it
+    let first_data_idx = selected_rows.as_ref()
+        .map(|rows| (rows.start() - 1).saturating_sub(first_sheet_row).max(data_start_idx))
+        .unwrap_or(data_start_idx)
+        .min(worksheet_rows.len());
+    let data_rows_with_merges = apply_column_merge_behaviors(
+        &raw_worksheet_rows[first_data_idx..],
+        &worksheet_rows[first_data_idx..],
+        &headers,
+        &column_merge_behaviors
+    );
+    
+    let default_coercion = CellCoercion::default();
+    
+    for (row_idx, row_data) in data_rows_with_merges.iter().enumerate() {
+        if row_data.values().all(is_blank_cell) {
+            continue; // Skip empty rows
+        }
+        
+        // Convert row data using enhanced conversion mappings, coercing each cell per its field's policy
+        let mut field_data = HashMap::new();
+        let mut field_columns = HashMap::new();
+        let mut coercion_failures = Vec::new();
+        for (excel_header, cell) in row_data {
+            if let Some(internal_field) = conversion_result.converted_headers.get(excel_header) {
+                let coercion = enhanced_conversion_map.field_definitions.get(internal_field)
+                    .map(|field_def| &field_def.cell_coercion)
+                    .unwrap_or(&default_coercion);
+                let value = coerce_cell(cell, coercion).unwrap_or_else(|problem| {
+                    coercion_failures.push((internal_field.clone(), problem));
+                    String::new()
+                });
+                field_data.insert(internal_field.clone(), value);
+                if let Some(column) = header_columns.get(excel_header.as_str()) {
+                    field_columns.insert(internal_field.clone(), column.clone());
+                }
+            }
+        }
+        
+        let source = RowSource {
+            sheet_name: sheet_name.to_string(),
+            row: first_sheet_row + first_data_idx + row_idx + 1,
+            columns: field_columns,
+        };
+        if selected_rows.as_ref().is_some_and(|rows| !rows.contains(&source.row)) {
+            continue;
+        }
+        report.total_rows += 1;
+        
+        // Rejected cells were left empty; report them once against their cell, not per member row
+        let has_coercion_failures = !coercion_failures.is_empty();
+        if has_coercion_failures {
+            coercion_failures.sort();
+            let coercion_failures: Vec<String> = coercion_failures.into_iter()
+                .map(|(field_name, problem)| match source.cell_reference(&field_name) {
+                    Some(cell_reference) => format!("{} ({}): {}", field_name, cell_reference, problem),
+                    None => format!("{}: {}", field_name, problem),
+                })
+                .collect();
+            report.row_issues.push(create_row_issue(&source, RowParseStatus::Degraded, Vec::new(), &coercion_failures));
+        }
+        
+        // Interface ranges such as "et-0/0/[10-13]" become one row per member
+        let member_rows = match &enhanced_conversion_map.port_range_expansion {
+            Some(expansion) => match expand_row_port_ranges(&field_data, &expansion.fields) {
+                Ok(member_rows) => member_rows,
+                Err(e) => {
+                    log::warn!("Skipping row {}: {}", source.row_reference(), e);
+                    let range_cells: Vec<String> = expansion.fields.iter()
+                        .filter_map(|field_name| source.cell_reference(field_name))
+                        .collect();
+                    report.row_issues.push(RowParseIssue {
+                        status: RowParseStatus::Skipped,
+                        error: ProcessingError {
+                            row_index: source.row,
+                            error_message: format!("Cannot expand {}: {}", range_cells.join(", "), e),
+                            error_type: ErrorType::ValidationError,
+                        },
+                        missing_required_fields: Vec::new(),
+                    });
+                    report.expanded_rows += 1;
+                    continue;
+                }
+            },
+            None => vec![field_data],
+        };
+        report.expanded_rows += member_rows.len();
+        
+        for field_data in member_rows {
+            // Apply field transformations
+            let (transformed_data, transformation_failures) = match service.apply_field_transformations_with_failures(&field_data, enhanced_conversion_map) {
+                Ok(result) => result,
+                Err(e) => {
+                    log::warn!("Failed to apply transformations to row {}: {}", source.row_reference(), e);
+                    report.row_issues.push(RowParseIssue {
+                        status: RowParseStatus::Skipped,
+                        error: ProcessingError {
+                            row_index: source.row,
+                            error_message: format!("Failed to apply transformations: {}", e),
+                            error_type: ErrorType::DataError,
+                        },
+                        missing_required_fields: Vec::new(),
+                    });
+                    continue;
+                }
+            };
+            
+            if !row_filter.is_empty() && !row_filter.accepts(&transformed_data) {
+                log::debug!("Row {} excluded by row filters", source.row_reference());
+                report.filtered_rows += 1;
+                continue;
+            }
+            
+            let missing_required_fields = find_missing_required_fields(&transformed_data, enhanced_conversion_map);
+            
+            // Convert to NetworkConfigRow using enhanced conversion results
+            if let Some(mut network_row) = convert_enhanced_to_network_config_row(&transformed_data, enhanced_conversion_map) {
+                // Member rows of a source row with rejected cells are degraded, but were reported above
+                if has_coercion_failures || !missing_required_fields.is_empty() || !transformation_failures.is_empty() {
+                    report.degraded_rows += 1;
+                }
+                if !missing_required_fields.is_empty() || !transformation_failures.is_empty() {
+                    report.row_issues.push(create_row_issue(&source, RowParseStatus::Degraded, missing_required_fields, &transformation_failures));
+                }
+                network_row.blueprint = blueprint_candidates.iter().find_map(|candidate| match candidate {
+                    BlueprintCandidate::Column => transformed_data.get("blueprint")
+                        .map(|value| value.trim())
+                        .filter(|value| !value.is_empty())
+                        .map(|value| value.to_string()),
+                    BlueprintCandidate::Fixed(value) => Some(value.clone()),
+                });
+                network_row.source = Some(source.clone());
+                rows.push(network_row);
+            } else {
+                log::warn!("Skipping row {} due to missing required fields: {:?}", source.row_reference(), missing_required_fields);
+                report.row_issues.push(create_row_issue(&source, RowParseStatus::Skipped, missing_required_fields, &transformation_failures));
+            }
+        }
+    }
+    
+    report.parsed_rows = rows.len();
+    report.skipped_rows = report.row_issues.iter().filter(|issue| issue.status == RowParseStatus::Skipped).count();
+    
+    Ok(ParsedSheetData { rows, report })
+}
+
+/// Blueprint source resolved for one sheet: the per-row column, or a value fixed for the whole sheet
+enum BlueprintCandidate {
+    Column,
+    Fixed(String),
+}
+
+/// Resolve the map's blueprint sources against a sheet, in order
+/// 
+/// Sheet-level sources (cell, sheet name, default) that yield no value are dropped, so a later
+/// source acts as the fallback. Without configured sources the `blueprint` column is used.
+fn resolve_blueprint_candidates(
+    worksheet: &Range<Data>,
+    sheet_name: &str,
+    enhanced_conversion_map: &EnhancedConversionMap
+) -> Result<Vec<BlueprintCandidate>, String> {
+    if enhanced_conversion_map.blueprint_sources.is_empty() {
+        return Ok(vec![BlueprintCandidate::Column]);
+    }
+    
+    let mut candidates = Vec::new();
+    for source in &enhanced_conversion_map.blueprint_sources {
+        let value = match source {
+            BlueprintSource::Column => {
+                candidates.push(BlueprintCandidate::Column);
+                continue;
+            }
+            BlueprintSource::Cell { cell } => {
+                let position = parse_cell_reference(cell)
+                    .ok_or_else(|| format!("Invalid blueprint cell reference '{}'", cell))?;
+                worksheet.get_value(position).map(|value| value.to_string())
+            }
+            BlueprintSource::SheetName { pattern } => {
+                let regex = regex::Regex::new(pattern)
+                    .map_err(|e| format!("Invalid blueprint sheet name pattern '{}': {}", pattern, e))?;
+                regex.captures(sheet_name).map(|captures| {
+                    captures.get(1).unwrap_or_else(|| captures.get(0).unwrap()).as_str().to_string()
+                })
+            }
+            BlueprintSource::Default { value } => Some(value.clone()),
+        };
+        
+        match value.map(|value| value.trim().to_string()).filter(|value| !value.is_empty()) {
+            Some(value) => {
+                log::debug!("Blueprint source {:?} resolved to '{}' for sheet '{}'", source, value, sheet_name);
+                candidates.push(BlueprintCandidate::Fixed(value));
+            }
+            None => log::debug!("Blueprint source {:?} has no value for sheet '{}'", source, sheet_name),
+        }
+    }
+    
+    Ok(candidates)
+}
+
+/// Group rows that share switch_label + switch_ifname when the group spans more than one sheet
+/// 
+/// Duplicates within a single sheet are left to row validation. Groups are ordered by first occurrence.
+pub fn find_cross_sheet_duplicates(rows: &[NetworkConfigRow]) -> Vec<DuplicateRowGroup> {
+    let mut groups: Vec<DuplicateRowGroup> = Vec::new();
+    let mut group_indices: HashMap<(String, String), usize> = HashMap::new();
+    
+    for row in rows {
+        let (Some(switch_label), Some(switch_ifname), Some(source)) = (&row.switch_label, &row.switch_ifname, &row.source) else {
+            continue;
+        };
+        let key = (switch_label.trim().to_string(), switch_ifname.trim().to_string());
+        let group_idx = *group_indices.entry(key.clone()).or_insert_with(|| {
+            groups.push(DuplicateRowGroup {
+                switch_label: key.0,
+                switch_ifname: key.1,
+                occurrences: Vec::new(),
+            });
+            groups.len() - 1
+        });
+        groups[group_idx].occurrences.push(source.clone());
+    }
+    
+    groups.into_iter()
+        .filter(|group| group.occurrences.iter().any(|source| source.sheet_name != group.occurrences[0].sheet_name))
+        .collect()
+}
+
+/// Required fields that are absent or empty in a converted row
+/// 
+/// switch_label and switch_ifname are always required since rows without them are dropped;
+/// the other fields are required when their field definition says so.
+fn find_missing_required_fields(field_data: &HashMap<String, String>, enhanced_conversion_map: &EnhancedConversionMap) -> Vec<String> {
+    let mut required_fields: Vec<&str> = enhanced_conversion_map.field_definitions.iter()
+        .filter(|(_, field_def)| field_def.is_required)
+        .map(|(field_name, _)| field_name.as_str())
+        .chain(["switch_label", "switch_ifname"])
+        .collect();
+    required_fields.sort();
+    required_fields.dedup();
+    
+    required_fields.into_iter()
+        .filter(|field_name| field_data.get(*field_name).is_none_or(|value| value.trim().is_empty()))
+        .map(|field_name| field_name.to_string())
+        .collect()
+}
+
+fn create_row_issue(
+    source: &RowSource,
+    status: RowParseStatus,
+    missing_required_fields: Vec<String>,
+    transformation_failures: &[String]
+) -> RowParseIssue {
+    let mut reasons = Vec::new();
+    if !missing_required_fields.is_empty() {
+        // Point at the empty cell when the field's column exists in the sheet
+        let missing_fields: Vec<String> = missing_required_fields.iter()
+            .map(|field_name| match source.cell_reference(field_name) {
+                Some(cell_reference) => format!("{} ({})", field_name, cell_reference),
+                None => field_name.clone(),
+            })
+            .collect();
+        reasons.push(format!("Missing required fields: {}", missing_fields.join(", ")));
+    }
+    reasons.extend(transformation_failures.iter().cloned());
+    
+    let error_type = if missing_required_fields.is_empty() {
+        ErrorType::DataError
+    } else {
+        ErrorType::ValidationError
+    };
+    
+    RowParseIssue {
+        status,
+        error: ProcessingError {
+            row_index: source.row,
+            error_message: reasons.join("; "),
+            error_type,
+        },
+        missing_required_fields,
+    }
+}
+
+/// Resolve the 0-based index of the header row within the worksheet rows, running header
+/// detection when the map asks for it
+/// 
+/// `header_row` and detected rows are 1-based sheet rows; `first_sheet_row` is the 0-based sheet
+/// row of the first worksheet row. Falls back to the configured `header_row` when detection finds
+/// no matching row. A configured row above the sheet's used range selects its first row.
+fn resolve_header_row_index(
+    worksheet_rows: &[Vec<Data>],
+    first_sheet_row: usize,
+    has_merge_metadata: bool,
+    enhanced_conversion_map: &EnhancedConversionMap
+) -> Result<usize, String> {
+    let configured_idx = (enhanced_conversion_map.header_row.unwrap_or(1).saturating_sub(1) as usize) // Convert 1-based to 0-based
+        .saturating_sub(first_sheet_row);
+    
+    let Some(detection_config) = enhanced_conversion_map.header_detection.as_ref()
+        .filter(|config| config.mode == HeaderDetectionMode::Auto) else {
+        return Ok(configured_idx);
+    };
+    
+    match detect_header_row(worksheet_rows, first_sheet_row, has_merge_metadata, enhanced_conversion_map, detection_config.max_rows)? {
+        Some(detection) => {
+            log::info!("Detected header row {} with confidence {:.2} (matched fields: {:?}, missing required: {:?})", 
+                       detection.header_row, detection.confidence, detection.matched_fields, detection.missing_required_fields);
+            Ok(detection.header_row as usize - 1 - first_sheet_row)
+        }
+        None => {
+            log::warn!("Header row detection found no matching row in the first {} rows, using configured header row {}", 
+                       detection_config.max_rows, first_sheet_row + configured_idx + 1);
+            Ok(configured_idx)
+        }
+    }
+}
+
+/// Detect the header row of a worksheet, scoring its first `max_rows` rows after merge expansion
+pub(crate) fn detect_worksheet_header_row(
+    worksheet: &Range<Data>,
+    merged_regions: Option<&[Dimensions]>,
+    enhanced_conversion_map: &EnhancedConversionMap,
+    max_rows: u32
+) -> Result<Option<HeaderRowDetection>, String> {
+    let expanded_worksheet = merged_regions.map(|regions| expand_merged_regions(worksheet, regions));
+    let worksheet_rows: Vec<Vec<Data>> = expanded_worksheet.as_ref().unwrap_or(worksheet)
+        .rows()
+        .map(|row| row.to_vec())
+        .collect();
+    let first_sheet_row = worksheet.start().map(|(row, _)| row as usize).unwrap_or(0);
+    
+    detect_header_row(&worksheet_rows, first_sheet_row, merged_regions.is_some(), enhanced_conversion_map, max_rows)
+}
+
+/// Score the first `max_rows` rows as header candidates against the conversion map
+/// 
+/// With a multi-row header span, each candidate is the composite header of the rows it starts.
+/// Candidates are reported by their 1-based sheet row, offset by the 0-based `first_sheet_row`.
+fn detect_header_row(
+    worksheet_rows: &[Vec<Data>],
+    first_sheet_row: usize,
+    has_merge_metadata: bool,
+    enhanced_conversion_map: &EnhancedConversionMap,
+    max_rows: u32
+) -> Result<Option<HeaderRowDetection>, String> {
+    let header_row_span = header_row_span(enhanced_conversion_map);
+    let candidate_rows: Vec<Vec<String>> = (0..worksheet_rows.len().min(max_rows as usize))
+        .map(|row_idx| {
+            let span_end = (row_idx + header_row_span).min(worksheet_rows.len());
+            extract_headers(&worksheet_rows[row_idx..span_end], has_merge_metadata)
+        })
+        .collect();
+    
+    let detection = EnhancedConversionService::new().detect_header_row(&candidate_rows, enhanced_conversion_map)?;
+    Ok(detection.map(|mut detection| {
+        let row_offset = first_sheet_row as u32;
+        detection.header_row += row_offset;
+        for candidate in &mut detection.candidates {
+            candidate.header_row += row_offset;
+        }
+        detection
+    }))
+}
+
+/// Number of rows the header occupies (1 unless the map configures a multi-row header)
+fn header_row_span(enhanced_conversion_map: &EnhancedConversionMap) -> usize {
+    enhanced_conversion_map.header_row_span.unwrap_or(1).max(1) as usize
+}
+
+/// Extract trimmed header strings from one or more header rows
+/// 
+/// A single header row is propagated row-wise when merge metadata is unavailable.
+/// Multi-row headers are combined level by level into composite headers, so a group header
+/// "Switch" above the sub-headers "Name" and "Port" yields "Switch Name" and "Switch Port".
+/// Empty levels are skipped and a level repeating the one above it (a vertically merged
+/// header cell) is only used once. Without merge metadata only the group levels are
+/// propagated row-wise; the bottom level is taken as-is.
+fn extract_headers(header_rows: &[Vec<Data>], has_merge_metadata: bool) -> Vec<String> {
+    let header_levels: Vec<Vec<String>> = header_rows.iter()
+        .enumerate()
+        .map(|(level_idx, header_row)| {
+            let is_group_level = level_idx + 1 < header_rows.len() || header_rows.len() == 1;
+            let header_cells = if has_merge_metadata || !is_group_level {
+                header_row.to_vec()
+            } else {
+                propagate_merged_cells_in_row(header_row)
+            };
+            header_cells
+                .iter()
+                .map(|cell| cell.to_string().trim().to_string())
+                .collect()
+        })
+        .collect();
+    
+    let column_count = header_levels.iter().map(|level| level.len()).max().unwrap_or(0);
+    (0..column_count)
+        .map(|col_idx| {
+            let mut parts: Vec<&str> = Vec::new();
+            for level in &header_levels {
+                match level.get(col_idx).map(|part| part.as_str()) {
+                    Some(part) if !part.is_empty() && parts.last() != Some(&part) => parts.push(part),
+                    _ => {}
+                }
+            }
+            parts.join(" ")
+        })
+        .collect()
+}
+
+/// Converts enhanced field data to NetworkConfigRow
+fn convert_enhanced_to_network_config_row(field_data: &HashMap<String, String>, enhanced_conversion_map: &EnhancedConversionMap) -> Option<NetworkConfigRow> {
+    // Extract required fields with empty string filtering
+    let switch_label = field_data.get("switch_label")
+        .filter(|s| !s.trim().is_empty())
+        .cloned();
+    let raw_switch_ifname = field_data.get("switch_ifname")
+        .filter(|s| !s.trim().is_empty())
+        .cloned();
+    
+    // Both switch name AND interface must be present - this prevents incomplete rows from reaching the provisioning table
+    if switch_label.is_none() || raw_switch_ifname.is_none() {
+        return None;
+    }
+    
+    let server_label = field_data.get("server_label")
+        .filter(|s| !s.trim().is_empty())
+        .cloned();
+    let server_ifname = field_data.get("server_ifname")
+        .filter(|s| !s.trim().is_empty())
+        .cloned();
+    let link_speed = field_data.get("link_speed")
+        .filter(|s| !s.trim().is_empty())
+        .cloned();
+    let link_group_lag_mode = field_data.get("link_group_lag_mode")
+        .filter(|s| !s.trim().is_empty())
+        .cloned()
+        .or(Some("none".to_string())); // Default to "none" when not present or empty
+    let link_group_ct_names = list_field(field_data, enhanced_conversion_map, "link_group_ct_names");
+    let link_group_ifname = field_data.get("link_group_ifname")
+        .filter(|s| !s.trim().is_empty())
+        .cloned();
+    let server_tags = list_field(field_data, enhanced_conversion_map, "server_tags");
+    let link_group_tags = field_data.get("switch_tags")
+        .filter(|s| !s.trim().is_empty())
+        .cloned(); // Map switch_tags to link_group_tags 
+    let link_tags = list_field(field_data, enhanced_conversion_map, "link_tags");
+    let is_external = field_data.get("is_external")
+        .filter(|s| !s.trim().is_empty())
+        .and_then(|val| {
+            // Handle boolean conversion from Enhanced Conversion Map transformations
+            match val.to_lowercase().as_str() {
+                "true" | "yes" | "y" | "1" => Some(true),
+                "false" | "no" | "n" | "0" => Some(false),
+                _ => val.parse::<bool>().ok()
+            }
+        })
+        .or(Some(false)); // Default to false (No) when not present
+    let comment = field_data.get("comment")
+        .filter(|s| !s.trim().is_empty())
+        .cloned();
+
+    Some(NetworkConfigRow {
+        blueprint: None,
+        server_label,
+        switch_label,
+        switch_ifname: raw_switch_ifname,
+        server_ifname,
+        link_speed,
+        link_group_lag_mode,
+        link_group_ct_names,
+        link_group_ifname,
+        is_external,
+        server_tags,
+        switch_tags: link_group_tags.clone(), // Map link_group_tags to switch_tags
+        link_group_tags,
+        link_tags,
+        comment,
+        source: None,
+    })
+}
+
+/// Items of a list field, split by the field's array format (or the default one)
+fn list_field(field_data: &HashMap<String, String>, enhanced_conversion_map: &EnhancedConversionMap, field_name: &str) -> Vec<String> {
+    let Some(value) = field_data.get(field_name) else {
+        return Vec::new();
+    };
+    match enhanced_conversion_map.field_definitions.get(field_name) {
+        Some(field_def) => field_def.array_format.split(value),
+        None => ArrayFormat::default().split(value),
+    }
+}
+
+/// Expand Excel merged regions using the sheet's merge metadata
+/// 
+/// In Excel, merged cells only have a value in the top-left cell of the merged range.
+/// This function copies that value into every cell covered by each merged region,
+/// regardless of whether the merge is vertical, horizontal or both.
+/// 
+/// Region coordinates are absolute sheet positions (as reported by calamine), so they are
+/// applied directly against the worksheet range even when it does not start at A1.
+/// Cells of a region that fall outside the worksheet range are ignored.
+pub fn expand_merged_regions(worksheet: &Range<Data>, merged_regions: &[Dimensions]) -> Range<Data> {
+    let mut expanded = worksheet.clone();
+    let (Some(range_start), Some(range_end)) = (worksheet.start(), worksheet.end()) else {
+        return expanded;
+    };
+    
+    for region in merged_regions {
+        let value = match worksheet.get_value(region.start) {
+            Some(value) if !value.is_empty() => value.clone(),
+            _ => continue,
+        };
+        
+        let first_row = region.start.0.max(range_start.0);
+        let last_row = region.end.0.min(range_end.0);
+        let first_col = region.start.1.max(range_start.1);
+        let last_col = region.end.1.min(range_end.1);
+        
+        for row in first_row..=last_row {
+            for col in first_col..=last_col {
+                expanded.set_value((row, col), value.clone());
+            }
+        }
+        
+        log::debug!("Expanded merged region {:?} with value '{}'", region, value);
+    }
+    
+    expanded
+}
+
+/// Build a raw preview of the first `max_rows` rows of a worksheet
+/// 
+/// Cells keep their type and raw value, no merge expansion or header mapping is applied.
+/// Cells covered by a merged region carry its span and the reference of its top-left cell.
+pub fn build_sheet_preview(
+    worksheet: &Range<Data>,
+    sheet_name: &str,
+    merged_regions: Option<&[Dimensions]>,
+    max_rows: usize
+) -> SheetPreview {
+    let (total_rows, total_columns) = worksheet.get_size();
+    let (first_row, first_col) = worksheet.start().unwrap_or((0, 0));
+    let merged_regions = merged_regions.unwrap_or(&[]);
+    
+    let rows = worksheet.rows()
+        .take(max_rows)
+        .enumerate()
+        .map(|(row_idx, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(col_idx, cell)| {
+                    let position = (first_row + row_idx as u32, first_col + col_idx as u32);
+                    let merge = merged_regions.iter()
+                        .find(|region| region.contains(position.0, position.1))
+                        .map(|region| MergeSpan {
+                            anchor: cell_position_reference(region.start),
+                            row_span: region.end.0 - region.start.0 + 1,
+                            col_span: region.end.1 - region.start.1 + 1,
+                            is_anchor: region.start == position,
+                        });
+                    let (cell_type, value) = preview_cell_value(cell);
+                    PreviewCell {
+                        reference: cell_position_reference(position),
+                        cell_type,
+                        value,
+                        merge,
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    
+    SheetPreview {
+        sheet_name: sheet_name.to_string(),
+        first_row: first_row as usize + 1,
+        first_column: column_letter(first_col),
+        total_rows,
+        total_columns,
+        has_merge_metadata: !merged_regions.is_empty(),
+        rows,
+    }
+}
+
+/// A1-style reference of a 0-based (row, column) position
+fn cell_position_reference(position: (u32, u32)) -> String {
+    format!("{}{}", column_letter(position.1), position.0 + 1)
+}
+
+/// Cell type and JSON value of a raw cell; date cells keep their Excel serial number
+fn preview_cell_value(cell: &Data) -> (PreviewCellType, serde_json::Value) {
+    match cell {
+        Data::Empty => (PreviewCellType::Empty, serde_json::Value::Null),
+        Data::String(value) => (PreviewCellType::String, serde_json::Value::from(value.clone())),
+        Data::Int(value) => (PreviewCellType::Int, serde_json::Value::from(*value)),
+        Data::Float(value) => (PreviewCellType::Float, serde_json::Value::from(*value)),
+        Data::Bool(value) => (PreviewCellType::Bool, serde_json::Value::from(*value)),
+        Data::DateTime(value) => (PreviewCellType::DateTime, serde_json::Value::from(value.as_f64())),
+        Data::DateTimeIso(value) => (PreviewCellType::DateTime, serde_json::Value::from(value.clone())),
+        Data::DurationIso(value) => (PreviewCellType::Duration, serde_json::Value::from(value.clone())),
+        Data::Error(error) => (PreviewCellType::Error, serde_json::Value::from(error.to_string())),
+    }
+}
+
+/// Convert data rows to header-keyed cell maps, filling empty cells per column merge behavior
+/// 
+/// - `WhenMerged`: uses the merge-expanded value, so only cells covered by a merged region are filled
+/// - `Never`: uses the raw cell value; cells inside a merged region other than its top-left stay empty
+/// - `Always`: uses the merge-expanded value and fills any remaining empty cell down from the
+///   nearest non-empty value above it in the same column
+/// 
+/// Entirely empty rows are left empty (so they are still skipped) and do not interrupt fill-down.
+fn apply_column_merge_behaviors(
+    raw_rows: &[Vec<Data>],
+    expanded_rows: &[Vec<Data>],
+    headers: &[String],
+    column_merge_behaviors: &[MergeBehavior]
+) -> Vec<HashMap<String, Data>> {
+    let cell_at = |row: &[Data], col_idx: usize| -> Data {
+        row.get(col_idx).cloned().unwrap_or(Data::Empty)
+    };
+    
+    let mut processed_rows = Vec::new();
+    let mut fill_down_values = vec![Data::Empty; headers.len()];
+    
+    for (raw_row, expanded_row) in raw_rows.iter().zip(expanded_rows) {
+        let row_is_empty = (0..headers.len()).all(|col_idx| is_blank_cell(&cell_at(expanded_row, col_idx)));
+        let mut row_map = HashMap::new();
+        
+        for (col_idx, header) in headers.iter().enumerate() {
+            let expanded_value = cell_at(expanded_row, col_idx);
+            let value = match column_merge_behaviors.get(col_idx).cloned().unwrap_or_default() {
+                MergeBehavior::Never => cell_at(raw_row, col_idx),
+                MergeBehavior::WhenMerged => expanded_value,
+                MergeBehavior::Always if row_is_empty => expanded_value,
+                MergeBehavior::Always if is_blank_cell(&expanded_value) => fill_down_values[col_idx].clone(),
+                MergeBehavior::Always => {
+                    fill_down_values[col_idx] = expanded_value.clone();
+                    expanded_value
+                }
+            };
+            row_map.insert(header.clone(), value);
+        }
+        
+        processed_rows.push(row_map);
+    }
+    
+    processed_rows
+}
+
+fn is_blank_cell(cell: &Data) -> bool {
+    cell.to_string().trim().is_empty()
+}
+
+/// Turn a typed cell into field text according to the field's coercion policy
+/// 
+/// Returns the problem instead of a value when the cell cannot be used as-is.
+fn coerce_cell(cell: &Data, coercion: &CellCoercion) -> Result<String, String> {
+    match cell {
+        Data::Error(error) if coercion.reject_errors => Err(format!("cell contains the error {}", error)),
+        Data::String(value) if coercion.reject_errors && EXCEL_ERROR_LITERALS.contains(&value.trim()) => {
+            Err(format!("cell contains the error {}", value.trim()))
+        }
+        Data::Float(value) if coercion.numbers == NumberCoercion::Integer => {
+            let rounded = value.round();
+            if (value - rounded).abs() <= INTEGER_TOLERANCE {
+                Ok(format!("{}", rounded as i64))
+            } else {
+                Err(format!("expected a whole number, got {}", value))
+            }
+        }
+        Data::DateTime(datetime) if !datetime.is_duration() && coercion.dates != DateCoercion::Serial => {
+            let datetime = datetime.as_datetime()
+                .ok_or_else(|| format!("date serial {} is out of range", datetime.as_f64()))?;
+            Ok(format_date(datetime, &coercion.dates))
+        }
+        Data::DateTimeIso(value) if matches!(coercion.dates, DateCoercion::MonthDay | DateCoercion::DayMonth) => {
+            let date = value.get(..10)
+                .and_then(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+                .ok_or_else(|| format!("unrecognized date {}", value))?;
+            Ok(format_date(date.and_time(chrono::NaiveTime::MIN), &coercion.dates))
+        }
+        _ => Ok(cell.to_string().trim().to_string()),
+    }
+}
+
+fn format_date(datetime: chrono::NaiveDateTime, date_coercion: &DateCoercion) -> String {
+    match date_coercion {
+        DateCoercion::MonthDay => format!("{}/{}", datetime.month(), datetime.day()),
+        DateCoercion::DayMonth => format!("{}/{}", datetime.day(), datetime.month()),
+        DateCoercion::Iso if datetime.time() == chrono::NaiveTime::MIN => datetime.format("%Y-%m-%d").to_string(),
+        DateCoercion::Iso | DateCoercion::Serial => datetime.format("%Y-%m-%dT%H:%M:%S").to_string(),
+    }
+}
+
+/// Propagate merged cell values within a single row
+/// 
+/// In Excel, merged cells only have a value in the first cell of the merged range.
+/// This function copies the value from the first non-empty cell to all subsequent 
+/// empty cells until the next non-empty cell is encountered.
+/// 
+/// This handles the common Excel pattern where merged cells appear as:
+/// ["Value1", "", "", "Value2", "", "Value3"]
+/// and transforms it to:
+/// ["Value1", "Value1", "Value1", "Value2", "Value2", "Value3"]
+/// 
+/// However, it includes validation to avoid propagating values that would create
+/// invalid data rows (e.g., header/category rows that span many columns).
+/// 
+/// Leading empty cells (before any value) are left as-is.
+fn propagate_merged_cells_in_row(row: &[Data]) -> Vec<Data> {
+    let mut result = row.to_vec();
+    let mut current_value: Option<Data> = None;
+    
+    for (i, cell) in row.iter().enumerate() {
+        if !cell.is_empty() {
+            current_value = Some(cell.clone());
+            result[i] = cell.clone();
+        } else if let Some(ref value) = current_value {
+            result[i] = value.clone();
+        }
+    }
+    
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::excel::services::row_filter::RowPredicate;
+    use crate::domains::excel::services::port_range::expand_port_range;
+
+    fn text(value: &str) -> Data {
+        Data::String(value.to_string())
+    }
+
+    fn parse_worksheet_data(
+        worksheet: &Range<Data>, 
+        sheet_name: &str,
+        merged_regions: Option<&[Dimensions]>,
+        enhanced_conversion_map: &EnhancedConversionMap
+    ) -> Result<Vec<NetworkConfigRow>, String> {
+        parse_worksheet_data_with_report(worksheet, sheet_name, merged_regions, enhanced_conversion_map, &SheetParseOptions::default())
+            .map(|parsed_sheet| parsed_sheet.rows)
+    }
+
+    /// Sheet with a title row, a header row and four data rows where the
+    /// server name is merged over rows 3-4 and the CT is merged over rows 3-6
+    fn create_merged_worksheet() -> (Range<Data>, Vec<Dimensions>) {
+        let mut worksheet: Range<Data> = Range::new((0, 0), (5, 4));
+        worksheet.set_value((0, 0), text("Rack A"));
+        for (col, header) in ["Switch Name", "Switch Interface", "Host Name", "CTs", "Comment"].iter().enumerate() {
+            worksheet.set_value((1, col as u32), text(header));
+        }
+        for (row, port) in ["xe-0/0/1", "xe-0/0/2", "xe-0/0/3", "xe-0/0/4"].iter().enumerate() {
+            worksheet.set_value((row as u32 + 2, 0), text("leaf-01"));
+            worksheet.set_value((row as u32 + 2, 1), text(port));
+        }
+        worksheet.set_value((2, 2), text("server-01"));
+        worksheet.set_value((4, 2), text("server-02"));
+        worksheet.set_value((2, 3), text("CT-PROD"));
+        worksheet.set_value((2, 4), text("spans two rows"));
+
+        let merged_regions = vec![
+            Dimensions::new((0, 0), (0, 4)),
+            Dimensions::new((2, 2), (3, 2)),
+            Dimensions::new((2, 3), (5, 3)),
+            Dimensions::new((2, 4), (3, 4)),
+        ];
+        (worksheet, merged_regions)
+    }
+
+    fn set_merge_behavior(map: &mut EnhancedConversionMap, field_name: &str, merge_behavior: MergeBehavior) {
+        map.field_definitions.get_mut(field_name).unwrap().merge_behavior = merge_behavior;
+    }
+
+    #[test]
+    fn test_merge_behavior_defaults_to_merged_regions_only() {
+        let map = EnhancedConversionService::load_default_enhanced_conversion_map().unwrap();
+        let (worksheet, merged_regions) = create_merged_worksheet();
+
+        let rows = parse_worksheet_data(&worksheet, "Sheet1", Some(&merged_regions), &map).unwrap();
+
+        assert_eq!(rows.len(), 4);
+        let server_labels: Vec<_> = rows.iter().map(|row| row.server_label.as_deref()).collect();
+        assert_eq!(server_labels, vec![Some("server-01"), Some("server-01"), Some("server-02"), None]);
+        assert!(rows.iter().all(|row| row.link_group_ct_names == ["CT-PROD"]));
+        assert_eq!(rows[1].comment.as_deref(), Some("spans two rows"));
+    }
+
+    #[test]
+    fn test_merge_behavior_always_and_never() {
+        let mut map = EnhancedConversionService::load_default_enhanced_conversion_map().unwrap();
+        set_merge_behavior(&mut map, "server_label", MergeBehavior::Always);
+        set_merge_behavior(&mut map, "comment", MergeBehavior::Never);
+        let (worksheet, merged_regions) = create_merged_worksheet();
+
+        let rows = parse_worksheet_data(&worksheet, "Sheet1", Some(&merged_regions), &map).unwrap();
+
+        assert_eq!(rows[3].server_label.as_deref(), Some("server-02"));
+        assert_eq!(rows[0].comment.as_deref(), Some("spans two rows"));
+        assert_eq!(rows[1].comment, None);
+    }
+
+    #[test]
+    fn test_fill_down_without_merge_metadata() {
+        let mut map = EnhancedConversionService::load_default_enhanced_conversion_map().unwrap();
+        set_merge_behavior(&mut map, "link_group_ct_names", MergeBehavior::Always);
+        let (worksheet, _) = create_merged_worksheet();
+
+        let rows = parse_worksheet_data(&worksheet, "Sheet1", None, &map).unwrap();
+
+        assert!(rows.iter().all(|row| row.link_group_ct_names == ["CT-PROD"]));
+        assert_eq!(rows[1].server_label, None);
+    }
+
+    #[test]
+    fn test_parse_report_lists_skipped_and_degraded_rows() {
+        let mut map = EnhancedConversionService::load_default_enhanced_conversion_map().unwrap();
+        map.header_row = Some(5);
+
+        // Used range starts on sheet row 4 with a title, header on sheet row 5
+        let mut worksheet: Range<Data> = Range::new((3, 0), (7, 4));
+        worksheet.set_value((3, 0), text("Rack A"));
+        for (col, header) in ["Switch Name", "Switch Interface", "Host Name", "Slot/Port", "Owner"].iter().enumerate() {
+            worksheet.set_value((4, col as u32), text(header));
+        }
+        for (col, value) in ["leaf-01", "xe-0/0/1", "server-01", "eth0", "ops"].iter().enumerate() {
+            worksheet.set_value((5, col as u32), text(value));
+        }
+        worksheet.set_value((6, 0), text("leaf-01"));
+        worksheet.set_value((6, 2), text("server-02"));
+        worksheet.set_value((7, 0), text("leaf-01"));
+        worksheet.set_value((7, 1), text("xe-0/0/3"));
+
+        let parsed_sheet = parse_worksheet_data_with_report(&worksheet, "Sheet1", Some(&[]), &map, &SheetParseOptions::default()).unwrap();
+        let report = &parsed_sheet.report;
+
+        assert_eq!(parsed_sheet.rows.len(), 2);
+        assert_eq!(report.header_row, Some(5));
+        assert_eq!(report.unmapped_headers, vec!["Owner".to_string()]);
+        assert_eq!((report.total_rows, report.parsed_rows, report.skipped_rows, report.degraded_rows), (3, 2, 1, 1));
+
+        let source = parsed_sheet.rows[0].source.as_ref().unwrap();
+        assert_eq!((source.sheet_name.as_str(), source.row), ("Sheet1", 6));
+        assert_eq!(source.cell_reference("server_ifname").as_deref(), Some("Sheet1!D6"));
+        assert_eq!(source.cell_reference("comment"), None);
+        assert_eq!(parsed_sheet.rows[1].source.as_ref().unwrap().row, 8);
+
+        assert_eq!(column_letter(25), "Z");
+        assert_eq!(column_letter(27), "AB");
+        let quoted_source = RowSource { sheet_name: "Rack A's".to_string(), row: 9, columns: HashMap::new() };
+        assert_eq!(quoted_source.row_reference(), "'Rack A''s'!9:9");
+
+        let skipped = &report.row_issues[0];
+        assert_eq!(skipped.status, RowParseStatus::Skipped);
+        assert_eq!(skipped.error.row_index, 7);
+        assert_eq!(skipped.missing_required_fields, vec!["server_ifname", "switch_ifname"]);
+        assert_eq!(skipped.error.error_message, "Missing required fields: server_ifname (Sheet1!D7), switch_ifname (Sheet1!B7)");
+
+        let degraded = &report.row_issues[1];
+        assert_eq!(degraded.status, RowParseStatus::Degraded);
+        assert_eq!(degraded.error.row_index, 8);
+        assert_eq!(degraded.missing_required_fields, vec!["server_ifname", "server_label"]);
+        assert!(matches!(degraded.error.error_type, ErrorType::ValidationError));
+    }
+
+    #[test]
+    fn test_range_and_row_filters_select_rack_rows() {
+        let map = EnhancedConversionService::load_default_enhanced_conversion_map().unwrap();
+
+        // Site-wide sheet: header on row 2, comment column E, rack B rows on sheet rows 5-7
+        let mut worksheet: Range<Data> = Range::new((0, 0), (7, 4));
+        for (col, header) in ["Switch Name", "Switch Interface", "Host Name", "Slot/Port", "Comment"].iter().enumerate() {
+            worksheet.set_value((1, col as u32), text(header));
+        }
+        let site_rows = [
+            ("leaf-a1", "xe-0/0/1", ""),
+            ("leaf-a2", "xe-0/0/1", ""),
+            ("leaf-b1", "xe-0/0/1", ""),
+            ("leaf-b1", "xe-0/0/2", "DECOM"),
+            ("leaf-b2", "xe-0/0/1", ""),
+            ("leaf-c1", "xe-0/0/1", ""),
+        ];
+        for (row, (switch, port, comment)) in site_rows.iter().enumerate() {
+            worksheet.set_value((row as u32 + 2, 0), text(switch));
+            worksheet.set_value((row as u32 + 2, 1), text(port));
+            worksheet.set_value((row as u32 + 2, 4), text(comment));
+        }
+
+        let parse = |options: SheetParseOptions| parse_worksheet_data_with_report(&worksheet, "Site", Some(&[]), &map, &options);
+        let switch_ports = |parsed_sheet: &ParsedSheetData| -> Vec<String> {
+            parsed_sheet.rows.iter()
+                .map(|row| format!("{}:{}", row.switch_label.as_deref().unwrap_or(""), row.switch_ifname.as_deref().unwrap_or("")))
+                .collect()
+        };
+
+        // Rows 5-7, columns A-D: the header above the range is still found and the comment column is cut
+        let ranged = parse(SheetParseOptions { range: Some("D7:A5".to_string()), ..Default::default() }).unwrap();
+        assert_eq!(switch_ports(&ranged), vec!["leaf-b1:xe-0/0/1", "leaf-b1:xe-0/0/2", "leaf-b2:xe-0/0/1"]);
+        assert_eq!(ranged.report.header_row, Some(2));
+        assert_eq!(ranged.report.total_rows, 3);
+        assert!(ranged.rows.iter().all(|row| row.comment.is_none()));
+
+        // Fill-down does not reach into the range from rows above it, but merged regions still do
+        let mut fill_map = map.clone();
+        set_merge_behavior(&mut fill_map, "server_label", MergeBehavior::Always);
+        let mut fill_sheet = worksheet.clone();
+        fill_sheet.set_value((3, 2), text("server-a2"));
+        fill_sheet.set_value((5, 2), text("server-b1"));
+        let server_labels = |merged_regions: &[Dimensions]| -> Vec<Option<String>> {
+            let options = SheetParseOptions { range: Some("A5:D7".to_string()), ..Default::default() };
+            parse_worksheet_data_with_report(&fill_sheet, "Site", Some(merged_regions), &fill_map, &options).unwrap()
+                .rows.into_iter().map(|row| row.server_label).collect()
+        };
+        assert_eq!(server_labels(&[]), vec![None, Some("server-b1".to_string()), Some("server-b1".to_string())]);
+        assert_eq!(server_labels(&[Dimensions::new((3, 2), (4, 2))]), vec![
+            Some("server-a2".to_string()), Some("server-b1".to_string()), Some("server-b1".to_string())
+        ]);
+
+        let filtered = parse(SheetParseOptions {
+            range: None,
+            include: vec![r#"switch_label ~ "^leaf-b""#.to_string()],
+            exclude: vec![r#"comment == "DECOM""#.to_string()],
+        }).unwrap();
+        assert_eq!(switch_ports(&filtered), vec!["leaf-b1:xe-0/0/1", "leaf-b2:xe-0/0/1"]);
+        assert_eq!((filtered.report.total_rows, filtered.report.filtered_rows), (6, 4));
+        assert_eq!(filtered.rows[1].source.as_ref().unwrap().row, 7);
+
+        assert!(parse(SheetParseOptions { range: Some("5:A".to_string()), ..Default::default() }).is_err());
+        assert!(parse(SheetParseOptions { include: vec!["switch_label ~ \"(\"".to_string()], ..Default::default() }).is_err());
+        assert!(RowPredicate::parse("switch_label leaf").is_err());
+        assert!(RowPredicate::parse(r#"comment != "DECOM"#).is_err());
+        assert!(RowPredicate::parse("comment != DECOM").unwrap().matches(&HashMap::new()));
+    }
+
+    #[test]
+    fn test_table_columns_replace_header_detection() {
+        use crate::models::enhanced_conversion_map::BlueprintSource;
+
+        let mut map = EnhancedConversionService::load_default_enhanced_conversion_map().unwrap();
+        map.blueprint_sources = vec![BlueprintSource::Cell { cell: "A1".to_string() }];
+
+        // Blueprint in A1, a notes table in A3:B4 and the connection table with its header on row 6
+        let mut worksheet: Range<Data> = Range::new((0, 0), (9, 3));
+        worksheet.set_value((0, 0), text("dc1-pod1"));
+        worksheet.set_value((2, 0), text("Note"));
+        worksheet.set_value((3, 0), text("Switch Name"));
+        for (col, header) in ["Switch Name", "Switch Interface", "Host Name"].iter().enumerate() {
+            worksheet.set_value((5, col as u32 + 1), text(header));
+        }
+        worksheet.set_value((6, 1), text("leaf-01"));
+        worksheet.set_value((6, 2), text("xe-0/0/1"));
+        worksheet.set_value((8, 1), text("leaf-01"));
+        worksheet.set_value((8, 2), text("xe-0/0/2"));
+        worksheet.set_value((9, 1), text("below the table"));
+
+        let columns: Vec<String> = ["Switch Name", "Switch Interface", "Host Name"].iter().map(|name| name.to_string()).collect();
+        let table_data = Dimensions::new((6, 1), (8, 3));
+        let parsed_table = parse_table_data_with_report(&worksheet, "Rack A", Some(&[]), &columns, table_data, &map, &SheetParseOptions::default()).unwrap();
+
+        assert_eq!(parsed_table.report.header_row, Some(6));
+        assert_eq!(parsed_table.report.total_rows, 2);
+        let rows = &parsed_table.rows;
+        assert_eq!(rows.iter().map(|row| row.switch_ifname.as_deref().unwrap()).collect::<Vec<_>>(), vec!["xe-0/0/1", "xe-0/0/2"]);
+        assert!(rows.iter().all(|row| row.blueprint.as_deref() == Some("dc1-pod1")));
+        assert_eq!(rows[1].source.as_ref().unwrap().cell_reference("switch_ifname").as_deref(), Some("'Rack A'!C9"));
+
+        let ranged_options = SheetParseOptions { range: Some("A1:D10".to_string()), ..Default::default() };
+        assert!(parse_table_data_with_report(&worksheet, "Rack A", Some(&[]), &columns, table_data, &map, &ranged_options).is_err());
+    }
+
+    #[test]
+    fn test_cell_coercion_recovers_dates_and_reports_rejected_cells() {
+        use crate::models::enhanced_conversion_map::CellCoercion;
+        use calamine::{CellErrorType, ExcelDateTime, ExcelDateTimeType};
+
+        let mut map = EnhancedConversionService::load_default_enhanced_conversion_map().unwrap();
+        map.field_definitions.get_mut("server_ifname").unwrap().cell_coercion = CellCoercion { dates: DateCoercion::MonthDay, ..Default::default() };
+        map.field_definitions.get_mut("link_speed").unwrap().cell_coercion = CellCoercion { numbers: NumberCoercion::Integer, ..Default::default() };
+
+        let mut worksheet: Range<Data> = Range::new((0, 0), (4, 5));
+        for (col, header) in ["Switch Name", "Switch Interface", "Host Name", "Slot/Port", "Speed", "Comment"].iter().enumerate() {
+            worksheet.set_value((1, col as u32), text(header));
+        }
+        for row in 2..5 {
+            worksheet.set_value((row, 0), text("leaf-01"));
+            worksheet.set_value((row, 1), text(&format!("xe-0/0/{}", row)));
+            worksheet.set_value((row, 2), text("server-01"));
+        }
+        // "1/1" typed into Excel became 2024-01-01, "3/12" became 2024-03-12
+        worksheet.set_value((2, 3), Data::DateTime(ExcelDateTime::new(45292.0, ExcelDateTimeType::DateTime, false)));
+        worksheet.set_value((3, 3), Data::DateTime(ExcelDateTime::new(45363.0, ExcelDateTimeType::DateTime, false)));
+        worksheet.set_value((4, 3), text("eth0"));
+        worksheet.set_value((2, 4), Data::Float(24.9999999999));
+        worksheet.set_value((3, 4), Data::Float(25.5));
+        worksheet.set_value((4, 4), Data::Int(100));
+        worksheet.set_value((2, 5), Data::Error(CellErrorType::NA));
+        worksheet.set_value((4, 5), text("#REF!"));
+
+        let parsed_sheet = parse_worksheet_data_with_report(&worksheet, "Sheet1", Some(&[]), &map, &SheetParseOptions::default()).unwrap();
+        let rows = &parsed_sheet.rows;
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows.iter().map(|row| row.server_ifname.as_deref()).collect::<Vec<_>>(), vec![Some("1/1"), Some("3/12"), Some("eth0")]);
+        assert_eq!(rows[0].comment, None);
+        assert_eq!(rows[2].comment, None);
+
+        let issues: Vec<_> = parsed_sheet.report.row_issues.iter()
+            .map(|issue| (issue.error.row_index, issue.status.clone(), issue.error.error_message.as_str()))
+            .collect();
+        assert_eq!(issues, vec![
+            (3, RowParseStatus::Degraded, "comment (Sheet1!F3): cell contains the error #N/A"),
+            (4, RowParseStatus::Degraded, "link_speed (Sheet1!E4): expected a whole number, got 25.5"),
+            (5, RowParseStatus::Degraded, "comment (Sheet1!F5): cell contains the error #REF!"),
+        ]);
+
+        let iso = CellCoercion { dates: DateCoercion::Iso, reject_errors: false, ..Default::default() };
+        assert_eq!(coerce_cell(&Data::DateTime(ExcelDateTime::new(45292.5, ExcelDateTimeType::DateTime, false)), &iso).unwrap(), "2024-01-01T12:00:00");
+        assert_eq!(coerce_cell(&Data::Error(CellErrorType::NA), &iso).unwrap(), "#N/A");
+        assert_eq!(coerce_cell(&Data::DateTime(ExcelDateTime::new(45292.0, ExcelDateTimeType::DateTime, false)), &CellCoercion::default()).unwrap(), "45292");
+        assert_eq!(coerce_cell(&Data::DateTimeIso("2024-02-03".to_string()), &CellCoercion { dates: DateCoercion::DayMonth, ..Default::default() }).unwrap(), "3/2");
+    }
+
+    #[test]
+    fn test_port_ranges_expand_into_member_rows() {
+        use calamine::CellErrorType;
+        use crate::models::enhanced_conversion_map::PortRangeExpansion;
+
+        let mut map = EnhancedConversionService::load_default_enhanced_conversion_map().unwrap();
+        map.port_range_expansion = Some(PortRangeExpansion { fields: vec!["switch_ifname".to_string(), "server_ifname".to_string()] });
+
+        let mut worksheet: Range<Data> = Range::new((0, 0), (4, 5));
+        for (col, header) in ["Switch Name", "Switch Interface", "Host Name", "Slot/Port", "AE", "Comment"].iter().enumerate() {
+            worksheet.set_value((1, col as u32), text(header));
+        }
+        for (col, value) in ["leaf-01", "et-0/0/[10-13]", "server-01", "eth[0-3]", "ae1"].iter().enumerate() {
+            worksheet.set_value((2, col as u32), text(value));
+        }
+        worksheet.set_value((2, 5), Data::Error(CellErrorType::NA));
+        for (col, value) in ["leaf-01", "et-0/0/[20-21]", "server-02", "eth[08-09]"].iter().enumerate() {
+            worksheet.set_value((3, col as u32), text(value));
+        }
+        for (col, value) in ["leaf-02", "et-0/0/[1-4]", "server-03", "eth[0-1]"].iter().enumerate() {
+            worksheet.set_value((4, col as u32), text(value));
+        }
+
+        let parsed_sheet = parse_worksheet_data_with_report(&worksheet, "Sheet1", Some(&[]), &map, &SheetParseOptions::default()).unwrap();
+        let members: Vec<_> = parsed_sheet.rows.iter()
+            .map(|row| (row.switch_ifname.as_deref().unwrap(), row.server_ifname.as_deref().unwrap(), row.source.as_ref().unwrap().row))
+            .collect();
+
+        assert_eq!(members, vec![
+            ("et-0/0/10", "eth0", 3), ("et-0/0/11", "eth1", 3), ("et-0/0/12", "eth2", 3), ("et-0/0/13", "eth3", 3),
+            ("et-0/0/20", "eth08", 4), ("et-0/0/21", "eth09", 4),
+        ]);
+        assert!(parsed_sheet.rows[..4].iter().all(|row| row.link_group_ifname.as_deref() == Some("ae1") && row.server_label.as_deref() == Some("server-01")));
+        // Source rows and expanded member rows are counted separately
+        let report = &parsed_sheet.report;
+        assert_eq!((report.total_rows, report.expanded_rows), (3, 7));
+        assert_eq!((report.parsed_rows, report.skipped_rows, report.degraded_rows), (6, 1, 4));
+        assert_eq!(report.parsed_rows + report.filtered_rows + report.skipped_rows, report.expanded_rows);
+
+        // The rejected cell is reported once for its source row, not once per member
+        let issues: Vec<_> = report.row_issues.iter()
+            .map(|issue| (issue.error.row_index, issue.status.clone(), issue.error.error_message.as_str()))
+            .collect();
+        assert_eq!(issues, vec![
+            (3, RowParseStatus::Degraded, "comment (Sheet1!F3): cell contains the error #N/A"),
+            (5, RowParseStatus::Skipped, "Cannot expand Sheet1!B5, Sheet1!D5: interface ranges have different member counts: switch_ifname has 4, server_ifname has 2"),
+        ]);
+
+        map.port_range_expansion = None;
+        let rows = parse_worksheet_data(&worksheet, "Sheet1", Some(&[]), &map).unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].switch_ifname.as_deref(), Some("et-0/0/[10-13]"));
+
+        assert_eq!(expand_port_range("xe-0/0/1").unwrap(), vec!["xe-0/0/1"]);
+        assert_eq!(expand_port_range("eth1-2").unwrap(), vec!["eth1-2"]);
+        assert!(expand_port_range("eth[3-1]").is_err());
+        assert!(expand_port_range("eth[0-999]").is_err());
+    }
+
+    #[test]
+    fn test_dashed_interface_names_pass_through_with_default_map() {
+        let map = EnhancedConversionService::load_default_enhanced_conversion_map().unwrap();
+        assert!(map.port_range_expansion.is_none());
+
+        let mut worksheet: Range<Data> = Range::new((0, 0), (3, 3));
+        for (col, header) in ["Switch Name", "Switch Interface", "Host Name", "Slot/Port"].iter().enumerate() {
+            worksheet.set_value((1, col as u32), text(header));
+        }
+        for (col, value) in ["leaf-01", "xe-0/0/1", "server-01", "eth1-2"].iter().enumerate() {
+            worksheet.set_value((2, col as u32), text(value));
+        }
+        for (col, value) in ["leaf-01", "xe-0/0/2", "server-02", "p3-1"].iter().enumerate() {
+            worksheet.set_value((3, col as u32), text(value));
+        }
+
+        let parsed_sheet = parse_worksheet_data_with_report(&worksheet, "Sheet1", Some(&[]), &map, &SheetParseOptions::default()).unwrap();
+        let server_ifnames: Vec<_> = parsed_sheet.rows.iter().map(|row| row.server_ifname.as_deref()).collect();
+        assert_eq!(server_ifnames, vec![Some("eth1-2"), Some("p3-1")]);
+        assert!(parsed_sheet.report.row_issues.is_empty());
+    }
+
+    #[test]
+    fn test_list_fields_are_split_cleaned_and_sorted() {
+        let mut map = EnhancedConversionService::load_default_enhanced_conversion_map().unwrap();
+
+        let mut worksheet: Range<Data> = Range::new((0, 0), (3, 4));
+        for (col, header) in ["Switch Name", "Switch Interface", "Server Tags", "CTs", "Link Tags"].iter().enumerate() {
+            worksheet.set_value((1, col as u32), text(header));
+        }
+        for (col, value) in ["leaf-01", "et-0/0/1", " web, prod\nweb ,", "CT-PROD,CT-MGMT", ""].iter().enumerate() {
+            worksheet.set_value((2, col as u32), text(value));
+        }
+        for (col, value) in ["leaf-01", "et-0/0/2", "prod,web", " CT-MGMT , CT-PROD"].iter().enumerate() {
+            worksheet.set_value((3, col as u32), text(value));
+        }
+
+        let rows = parse_worksheet_data(&worksheet, "Sheet1", Some(&[]), &map).unwrap();
+        assert_eq!(rows[0].server_tags, ["prod", "web"]);
+        assert_eq!(rows[0].server_tags, rows[1].server_tags);
+        assert_eq!(rows[0].link_group_ct_names, ["CT-MGMT", "CT-PROD"]);
+        assert_eq!(rows[0].link_group_ct_names, rows[1].link_group_ct_names);
+        assert!(rows[0].link_tags.is_empty());
+
+        let server_tags = map.field_definitions.get_mut("server_tags").unwrap();
+        server_tags.array_format.sort = false;
+        server_tags.array_format.dedupe = false;
+        let rows = parse_worksheet_data(&worksheet, "Sheet1", Some(&[]), &map).unwrap();
+        assert_eq!(rows[0].server_tags, ["web", "prod", "web"]);
+    }
+
+    #[test]
+    fn test_cross_sheet_duplicates_are_reported() {
+        let map = EnhancedConversionService::load_default_enhanced_conversion_map().unwrap();
+        let rack_sheet = |ports: &[&str]| {
+            let mut worksheet: Range<Data> = Range::new((0, 0), (ports.len() as u32 + 1, 1));
+            worksheet.set_value((1, 0), text("Switch Name"));
+            worksheet.set_value((1, 1), text("Switch Interface"));
+            for (row, port) in ports.iter().enumerate() {
+                worksheet.set_value((row as u32 + 2, 0), text("leaf-01"));
+                worksheet.set_value((row as u32 + 2, 1), text(port));
+            }
+            worksheet
+        };
+
+        // xe-0/0/1 is repeated within Rack A only, xe-0/0/2 appears on both sheets
+        let mut rows = parse_worksheet_data(&rack_sheet(&["xe-0/0/1", "xe-0/0/1", "xe-0/0/2"]), "Rack A", Some(&[]), &map).unwrap();
+        rows.extend(parse_worksheet_data(&rack_sheet(&["xe-0/0/3", "xe-0/0/2"]), "Rack B", Some(&[]), &map).unwrap());
+
+        let duplicates = find_cross_sheet_duplicates(&rows);
+
+        assert_eq!(rows.len(), 5);
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].switch_ifname, "xe-0/0/2");
+        let occurrences: Vec<_> = duplicates[0].occurrences.iter()
+            .map(|source| source.cell_reference("switch_ifname").unwrap())
+            .collect();
+        assert_eq!(occurrences, vec!["'Rack A'!B5", "'Rack B'!B4"]);
+    }
+
+    #[test]
+    fn test_sheet_preview_keeps_cell_types_and_merge_spans() {
+        let (mut worksheet, merged_regions) = create_merged_worksheet();
+        worksheet.set_value((5, 4), Data::Float(25.5));
+
+        let preview = build_sheet_preview(&worksheet, "Rack A", Some(&merged_regions), 4);
+
+        assert_eq!((preview.total_rows, preview.total_columns), (6, 5));
+        assert_eq!((preview.first_row, preview.first_column.as_str()), (1, "A"));
+        assert_eq!(preview.rows.len(), 4);
+
+        let title = &preview.rows[0][0];
+        assert_eq!(title.cell_type, PreviewCellType::String);
+        assert_eq!(title.merge, Some(MergeSpan { anchor: "A1".to_string(), row_span: 1, col_span: 5, is_anchor: true }));
+
+        // Covered cells stay empty in the raw preview but point at the merge anchor
+        let covered = &preview.rows[3][3];
+        assert_eq!(covered.reference, "D4");
+        assert_eq!(covered.cell_type, PreviewCellType::Empty);
+        assert_eq!(covered.merge.as_ref().map(|merge| (merge.anchor.as_str(), merge.row_span, merge.is_anchor)), Some(("D3", 4, false)));
+        assert_eq!(preview.rows[2][1].merge, None);
+
+        let full_preview = build_sheet_preview(&worksheet, "Rack A", None, 100);
+        assert_eq!(full_preview.rows.len(), 6);
+        assert_eq!(full_preview.rows[5][4].cell_type, PreviewCellType::Float);
+        assert_eq!(full_preview.rows[5][4].value, serde_json::json!(25.5));
+    }
+
+    #[test]
+    fn test_blueprint_sources_fall_back_in_order() {
+        use crate::models::enhanced_conversion_map::{BlueprintSource, FieldDefinition};
+
+        let mut map = EnhancedConversionService::load_default_enhanced_conversion_map().unwrap();
+        let mut blueprint_field: FieldDefinition = map.field_definitions["comment"].clone();
+        blueprint_field.display_name = "Blueprint".to_string();
+        blueprint_field.xlsx_mappings[0].pattern = "Blueprint".to_string();
+        blueprint_field.xlsx_mappings.truncate(1);
+        map.field_definitions.insert("blueprint".to_string(), blueprint_field);
+
+        // Blueprint name in B1 above the header row, per-row override column
+        let mut worksheet: Range<Data> = Range::new((0, 0), (4, 2));
+        worksheet.set_value((0, 0), text("Blueprint:"));
+        worksheet.set_value((0, 1), text("dc1-pod1"));
+        for (col, header) in ["Switch Name", "Switch Interface", "Blueprint"].iter().enumerate() {
+            worksheet.set_value((1, col as u32), text(header));
+        }
+        for (row, port) in ["xe-0/0/1", "xe-0/0/2", "xe-0/0/3"].iter().enumerate() {
+            worksheet.set_value((row as u32 + 2, 0), text("leaf-01"));
+            worksheet.set_value((row as u32 + 2, 1), text(port));
+        }
+        worksheet.set_value((3, 2), text("dc1-pod2"));
+
+        let blueprints = |map: &EnhancedConversionMap, sheet_name: &str| -> Vec<Option<String>> {
+            parse_worksheet_data(&worksheet, sheet_name, Some(&[]), map).unwrap()
+                .into_iter()
+                .map(|row| row.blueprint)
+                .collect()
+        };
+
+        // Without configured sources only the blueprint column is used
+        assert_eq!(blueprints(&map, "Rack A"), vec![None, Some("dc1-pod2".to_string()), None]);
+
+        map.blueprint_sources = vec![
+            BlueprintSource::Column,
+            BlueprintSource::SheetName { pattern: r"^BP-(\w+)".to_string() },
+            BlueprintSource::Cell { cell: "$D$9".to_string() },
+            BlueprintSource::Cell { cell: "B1".to_string() },
+        ];
+        assert_eq!(blueprints(&map, "BP-dc2"), vec![Some("dc2".to_string()), Some("dc1-pod2".to_string()), Some("dc2".to_string())]);
+        assert_eq!(blueprints(&map, "Rack A")[0].as_deref(), Some("dc1-pod1"));
+
+        map.blueprint_sources = vec![BlueprintSource::SheetName { pattern: "^BP-".to_string() }, BlueprintSource::Default { value: "fallback".to_string() }];
+        assert_eq!(blueprints(&map, "BP-dc2")[0].as_deref(), Some("BP-"));
+        assert_eq!(blueprints(&map, "Rack A")[0].as_deref(), Some("fallback"));
+
+        map.blueprint_sources = vec![BlueprintSource::SheetName { pattern: "(".to_string() }];
+        assert!(parse_worksheet_data(&worksheet, "Rack A", Some(&[]), &map).is_err());
+    }
+
+    #[test]
+    fn test_auto_header_detection_finds_shifted_header() {
+        use crate::models::enhanced_conversion_map::HeaderDetectionConfig;
+
+        let mut map = EnhancedConversionService::load_default_enhanced_conversion_map().unwrap();
+        map.header_detection = Some(HeaderDetectionConfig { mode: HeaderDetectionMode::Auto, max_rows: 10 });
+
+        // Vendor layout: title block, blank line and notes before the header on row 5
+        let mut worksheet: Range<Data> = Range::new((0, 0), (6, 3));
+        worksheet.set_value((0, 0), text("Site survey"));
+        worksheet.set_value((2, 0), text("Notes: see comment column"));
+        for (col, header) in ["Switch Name", "Switch Interface", "Host Name", "Slot/Port"].iter().enumerate() {
+            worksheet.set_value((4, col as u32), text(header));
+        }
+        for (col, value) in ["leaf-01", "xe-0/0/1", "server-01", "eth0"].iter().enumerate() {
+            worksheet.set_value((5, col as u32), text(value));
+        }
+
+        let rows = parse_worksheet_data(&worksheet, "Sheet1", Some(&[]), &map).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].server_ifname.as_deref(), Some("eth0"));
+
+        let worksheet_rows: Vec<Vec<Data>> = worksheet.rows().map(|row| row.to_vec()).collect();
+        let detection = detect_header_row(&worksheet_rows, 0, true, &map, 10).unwrap().unwrap();
+        assert_eq!(detection.header_row, 5);
+        assert_eq!(detection.confidence, 1.0);
+        assert!(detection.missing_required_fields.is_empty());
+    }
+
+    #[test]
+    fn test_detected_header_row_is_a_sheet_row() {
+        use crate::models::enhanced_conversion_map::HeaderDetectionConfig;
+
+        let mut map = EnhancedConversionService::load_default_enhanced_conversion_map().unwrap();
+        map.header_detection = Some(HeaderDetectionConfig { mode: HeaderDetectionMode::Auto, max_rows: 10 });
+
+        // Rows 1-2 are blank, so the used range starts at row 3 with the header on row 4
+        let mut worksheet: Range<Data> = Range::new((2, 0), (4, 3));
+        worksheet.set_value((2, 0), text("Site survey"));
+        for (col, header) in ["Switch Name", "Switch Interface", "Host Name", "Slot/Port"].iter().enumerate() {
+            worksheet.set_value((3, col as u32), text(header));
+        }
+        for (col, value) in ["leaf-01", "xe-0/0/1", "server-01", "eth0"].iter().enumerate() {
+            worksheet.set_value((4, col as u32), text(value));
+        }
+
+        let detection = detect_worksheet_header_row(&worksheet, Some(&[]), &map, 10).unwrap().unwrap();
+        assert_eq!(detection.header_row, 4);
+        assert_eq!(detection.candidates.iter().map(|candidate| candidate.header_row).collect::<Vec<_>>(), vec![3, 4, 5]);
+
+        let detected = parse_worksheet_data_with_report(&worksheet, "Sheet1", Some(&[]), &map, &SheetParseOptions::default()).unwrap();
+        assert_eq!(detected.report.header_row, Some(4));
+
+        // Feeding the detected row back as the configured header row selects the same row
+        map.header_detection = None;
+        map.header_row = Some(detection.header_row);
+        let configured = parse_worksheet_data_with_report(&worksheet, "Sheet1", Some(&[]), &map, &SheetParseOptions::default()).unwrap();
+        assert_eq!(configured.report.header_row, Some(4));
+        assert_eq!(configured.rows.len(), 1);
+        assert_eq!(configured.rows[0].server_ifname.as_deref(), Some("eth0"));
+    }
+
+    #[test]
+    fn test_two_level_header_builds_composite_headers() {
+        let mut map = EnhancedConversionService::load_default_enhanced_conversion_map().unwrap();
+        map.header_row = Some(1);
+        map.header_row_span = Some(2);
+
+        // | Server      | Switch      | Speed |
+        // | Name | Port | Name | Port |       |
+        let mut worksheet: Range<Data> = Range::new((0, 0), (2, 4));
+        worksheet.set_value((0, 0), text("Server"));
+        worksheet.set_value((0, 2), text("Switch"));
+        worksheet.set_value((0, 4), text("Speed"));
+        for (col, header) in ["Name", "Port", "Name", "Port"].iter().enumerate() {
+            worksheet.set_value((1, col as u32), text(header));
+        }
+        for (col, value) in ["server-01", "eth1", "leaf-01", "xe-0/0/7", "10G"].iter().enumerate() {
+            worksheet.set_value((2, col as u32), text(value));
+        }
+        let merged_regions = vec![
+            Dimensions::new((0, 0), (0, 1)),
+            Dimensions::new((0, 2), (0, 3)),
+            Dimensions::new((0, 4), (1, 4)),
+        ];
+
+        let expanded = expand_merged_regions(&worksheet, &merged_regions);
+        let header_rows: Vec<Vec<Data>> = expanded.rows().take(2).map(|row| row.to_vec()).collect();
+        assert_eq!(
+            extract_headers(&header_rows, true),
+            vec!["Server Name", "Server Port", "Switch Name", "Switch Port", "Speed"]
+        );
+
+        let rows = parse_worksheet_data(&worksheet, "Sheet1", Some(&merged_regions), &map).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].server_label.as_deref(), Some("server-01"));
+        assert_eq!(rows[0].server_ifname.as_deref(), Some("eth1"));
+        assert_eq!(rows[0].switch_label.as_deref(), Some("leaf-01"));
+        assert_eq!(rows[0].switch_ifname.as_deref(), Some("xe-0/0/7"));
+        assert_eq!(rows[0].link_speed.as_deref(), Some("10G"));
+    }
+}
@@ -31,8 +31,17 @@ mod excel_processing_tests {
     #[test]
     fn test_excel_processing_service_creation() {
         let service = ExcelProcessingService::new();
-        // Test that service can be created
-        assert_eq!(service.get_sheet_names("test.xlsx").unwrap(), vec!["Sheet1"]);
+        // Test that service can be created and only reads registered uploads
+        let result = service.get_sheet_names("nonexistent.xlsx");
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Unknown or expired file id"));
+
+        // Existing files are not read by path either
+        let file_path = std::env::temp_dir().join(format!("unregistered_{}.xlsx", uuid::Uuid::new_v4()));
+        std::fs::write(&file_path, b"workbook bytes").unwrap();
+        let result = service.get_sheet_names(&file_path.to_string_lossy());
+        std::fs::remove_file(&file_path).unwrap();
+        assert!(result.unwrap_err().contains("Unknown or expired file id"));
     }
 
     #[test]
@@ -42,4 +51,68 @@ mod excel_processing_tests {
         let result = rt.block_on(cleanup_temp_file("test_file_id".to_string()));
         assert!(result.is_ok());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_expand_merged_regions_vertical_and_horizontal() {
+        use calamine::{Data, Dimensions, Range};
+
+        let mut worksheet: Range<Data> = Range::new((0, 0), (24, 3));
+        worksheet.set_value((0, 0), Data::String("Rack A".to_string()));
+        worksheet.set_value((2, 3), Data::String("CT-PROD".to_string()));
+
+        let merged_regions = vec![
+            Dimensions::new((0, 0), (0, 3)),  // Horizontal rack header
+            Dimensions::new((2, 3), (21, 3)), // 20-row CT merge
+        ];
+
+        let expanded = expand_merged_regions(&worksheet, &merged_regions);
+
+        for col in 0..=3 {
+            assert_eq!(expanded.get_value((0, col)), Some(&Data::String("Rack A".to_string())));
+        }
+        for row in 2..=21 {
+            assert_eq!(expanded.get_value((row, 3)), Some(&Data::String("CT-PROD".to_string())));
+        }
+        assert_eq!(expanded.get_value((22, 3)), Some(&Data::Empty));
+        assert_eq!(expanded.get_value((3, 2)), Some(&Data::Empty));
+    }
+
+    #[test]
+    fn test_expand_merged_regions_with_offset_range() {
+        use calamine::{Data, Dimensions, Range};
+
+        // Worksheet ranges start at the first used cell, not necessarily A1
+        let mut worksheet: Range<Data> = Range::new((3, 1), (6, 2));
+        worksheet.set_value((4, 2), Data::String("server-01".to_string()));
+
+        let expanded = expand_merged_regions(&worksheet, &[Dimensions::new((4, 2), (8, 2))]);
+
+        assert_eq!(expanded.get_size(), worksheet.get_size());
+        assert_eq!(expanded.get_value((6, 2)), Some(&Data::String("server-01".to_string())));
+        assert_eq!(expanded.get_value((3, 2)), Some(&Data::Empty));
+    }
+
+    #[test]
+    fn test_validate_file_type_accepts_supported_spreadsheet_formats() {
+        use crate::utils::file_utils::FileUtils;
+
+        for file_path in ["survey.xlsx", "survey.xlsm", "legacy.XLS", "binary.xlsb", "libreoffice.ods"] {
+            assert!(FileUtils::validate_file_type(file_path), "{} should be accepted", file_path);
+        }
+        for file_path in ["notes.txt", "export.csv", "xlsx", "archive.xlsx.zip"] {
+            assert!(!FileUtils::validate_file_type(file_path), "{} should be rejected", file_path);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upload_excel_file_rejects_unsupported_extension() {
+        let file_path = std::env::temp_dir().join(format!("upload_test_{}.txt", uuid::Uuid::new_v4()));
+        std::fs::write(&file_path, "not a spreadsheet").unwrap();
+
+        let result = upload_excel_file(file_path.to_string_lossy().to_string()).await;
+        std::fs::remove_file(&file_path).unwrap();
+
+        let error_msg = result.unwrap_err();
+        assert!(error_msg.contains(".xls, .xlsb, .ods"), "Expected supported extension list, got: {}", error_msg);
+    }
+}
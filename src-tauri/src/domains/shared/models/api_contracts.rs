@@ -2,6 +2,7 @@
 // These types define the contracts between frontend and backend across all domains
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 // ============================================================================
 // Core Data Types
@@ -12,18 +13,90 @@ pub struct NetworkConfigRow {
     pub blueprint: Option<String>,
     pub server_label: Option<String>,
     pub is_external: Option<bool>,
-    pub server_tags: Option<String>,
+    #[serde(default)]
+    pub server_tags: Vec<String>,
     pub switch_tags: Option<String>,
     pub link_group_ifname: Option<String>,
     pub link_group_lag_mode: Option<String>,
-    pub link_group_ct_names: Option<String>,
+    #[serde(default)]
+    pub link_group_ct_names: Vec<String>,
     pub link_group_tags: Option<String>,
     pub link_speed: Option<String>,
     pub server_ifname: Option<String>,
     pub switch_label: Option<String>,
     pub switch_ifname: Option<String>,
-    pub link_tags: Option<String>,
+    #[serde(default)]
+    pub link_tags: Vec<String>,
     pub comment: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<RowSource>,
+}
+
+/// Where a parsed row came from in the spreadsheet
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct RowSource {
+    pub sheet_name: String,
+    /// 1-based sheet row
+    pub row: usize,
+    /// Field name -> source column letter (e.g. "F")
+    pub columns: HashMap<String, String>,
+}
+
+impl RowSource {
+    /// A1-style reference of the cell a field was read from, e.g. "Sheet2!F37"
+    /// 
+    /// Sheet names that are not plain identifiers are quoted the way Excel does ('Rack A'!F37).
+    pub fn cell_reference(&self, field_name: &str) -> Option<String> {
+        let column = self.columns.get(field_name)?;
+        Some(format!("{}!{}{}", quote_sheet_name(&self.sheet_name), column, self.row))
+    }
+
+    /// Reference of the whole source row, e.g. "Sheet2!37:37"
+    pub fn row_reference(&self) -> String {
+        format!("{}!{}:{}", quote_sheet_name(&self.sheet_name), self.row, self.row)
+    }
+}
+
+/// Convert a 0-based column index to its spreadsheet letter (0 -> "A", 26 -> "AA")
+pub fn column_letter(col_idx: u32) -> String {
+    let mut letters = Vec::new();
+    let mut remaining = col_idx as u64 + 1;
+    while remaining > 0 {
+        let letter_idx = ((remaining - 1) % 26) as u8;
+        letters.push((b'A' + letter_idx) as char);
+        remaining = (remaining - 1) / 26;
+    }
+    letters.iter().rev().collect()
+}
+
+/// Parse an A1-style cell reference ("B1", "$B$1") into a 0-based (row, column) position
+pub fn parse_cell_reference(reference: &str) -> Option<(u32, u32)> {
+    let reference = reference.trim().replace('$', "").to_ascii_uppercase();
+    let letters_end = reference.find(|c: char| !c.is_ascii_alphabetic())?;
+    let (letters, digits) = reference.split_at(letters_end);
+    if letters.is_empty() || letters.len() > 3 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let col = letters.bytes().fold(0u32, |col, letter| col * 26 + (letter - b'A' + 1) as u32) - 1;
+    let row = digits.parse::<u32>().ok()?.checked_sub(1)?;
+    Some((row, col))
+}
+
+/// Parse an A1-style cell range ("A5:N400") into 0-based (start, end) positions
+pub fn parse_cell_range(range: &str) -> Option<((u32, u32), (u32, u32))> {
+    let (start, end) = range.split_once(':')?;
+    let (start, end) = (parse_cell_reference(start)?, parse_cell_reference(end)?);
+    // Accept ranges written in any corner order, like Excel does
+    Some(((start.0.min(end.0), start.1.min(end.1)), (start.0.max(end.0), start.1.max(end.1))))
+}
+
+fn quote_sheet_name(sheet_name: &str) -> String {
+    if !sheet_name.is_empty() && sheet_name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        sheet_name.to_string()
+    } else {
+        format!("'{}'", sheet_name.replace('\'', "''"))
+    }
 }
 
 // ============================================================================
@@ -161,6 +234,181 @@ pub struct ExcelSheetData {
     pub total_rows: usize,
 }
 
+/// Optional restrictions on which cells and rows of a sheet are parsed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SheetParseOptions {
+    /// A1-style cell range such as "A5:N400" limiting data rows and columns; the header row may lie above it
+    #[serde(default)]
+    pub range: Option<String>,
+    /// Row predicates that must all match, e.g. switch_label ~ "^leaf-a"
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Rows matching any of these predicates are dropped, e.g. comment == "DECOM"
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+/// A workbook copied into the app's temp directory, referred to by `file_id` in later commands
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadedFile {
+    pub file_id: String,
+    pub file_name: String,
+    pub size: u64,
+    pub sha256: String,
+    pub sheet_names: Vec<String>,
+}
+
+/// A named Excel table (ListObject) that can be parsed instead of a whole sheet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExcelTableInfo {
+    pub name: String,
+    pub sheet_name: String,
+    /// Table column names, used as headers
+    pub columns: Vec<String>,
+    /// A1-style range of the table body, e.g. "B4:G40"
+    pub range: String,
+    pub row_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParsedSheetData {
+    pub rows: Vec<NetworkConfigRow>,
+    pub report: ParseReport,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParsedWorkbookData {
+    /// Rows of all sheets, in sheet order
+    pub rows: Vec<NetworkConfigRow>,
+    pub sheet_reports: Vec<SheetParseReport>,
+    /// switch_label + switch_ifname found on more than one sheet
+    pub duplicates: Vec<DuplicateRowGroup>,
+}
+
+/// Row-level changes between two versions of a workbook, keyed by switch_label + switch_ifname
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct WorkbookDiff {
+    /// Rows of the new version only
+    pub added: Vec<NetworkConfigRow>,
+    /// Rows of the old version only
+    pub removed: Vec<NetworkConfigRow>,
+    pub modified: Vec<ModifiedRow>,
+    pub unchanged_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModifiedRow {
+    pub switch_label: String,
+    pub switch_ifname: String,
+    pub changes: Vec<FieldChange>,
+    /// Where the row was in the old version
+    pub previous_source: Option<RowSource>,
+    /// The row as it is in the new version
+    pub row: NetworkConfigRow,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SheetParseReport {
+    pub sheet_name: String,
+    pub report: ParseReport,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateRowGroup {
+    pub switch_label: String,
+    pub switch_ifname: String,
+    pub occurrences: Vec<RowSource>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ParseReport {
+    /// 1-based sheet row of the (first) header row
+    pub header_row: Option<usize>,
+    pub unmapped_headers: Vec<String>,
+    /// Non-empty data rows
+    pub total_rows: usize,
+    /// Rows after port range expansion; parsed, filtered and skipped rows count these
+    #[serde(default)]
+    pub expanded_rows: usize,
+    pub parsed_rows: usize,
+    /// Rows dropped by the include/exclude row filters
+    #[serde(default)]
+    pub filtered_rows: usize,
+    pub skipped_rows: usize,
+    pub degraded_rows: usize,
+    pub row_issues: Vec<RowParseIssue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RowParseIssue {
+    pub status: RowParseStatus,
+    /// The issue, with row_index as the 1-based sheet row
+    #[serde(flatten)]
+    pub error: ProcessingError,
+    pub missing_required_fields: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RowParseStatus {
+    /// Row was dropped from the results
+    Skipped,
+    /// Row was kept but is incomplete or not fully transformed
+    Degraded,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SheetPreview {
+    pub sheet_name: String,
+    /// 1-based sheet row of the first preview row
+    pub first_row: usize,
+    /// Column letter of the first cell in each row
+    pub first_column: String,
+    /// Rows in the sheet's used range, not just the preview
+    pub total_rows: usize,
+    pub total_columns: usize,
+    pub has_merge_metadata: bool,
+    pub rows: Vec<Vec<PreviewCell>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewCell {
+    /// A1-style reference, e.g. "B3"
+    pub reference: String,
+    pub cell_type: PreviewCellType,
+    pub value: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merge: Option<MergeSpan>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PreviewCellType {
+    Empty,
+    String,
+    Int,
+    Float,
+    Bool,
+    /// Excel serial number or ISO 8601 string
+    DateTime,
+    Duration,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MergeSpan {
+    /// Reference of the merged region's top-left cell
+    pub anchor: String,
+    pub row_span: u32,
+    pub col_span: u32,
+    pub is_anchor: bool,
+}
+
 // ============================================================================
 // Domain Event Types
 // ============================================================================
@@ -36,6 +36,15 @@ fn main() {
             greet,
             upload_excel_file,
             parse_excel_sheet,
+            parse_excel_sheet_with_report,
+            parse_excel_sheets,
+            diff_excel_workbooks,
+            list_excel_tables,
+            parse_excel_table,
+            detect_excel_header_row,
+            preview_excel_sheet,
+            parse_csv_file,
+            parse_csv_file_with_report,
             process_import_generic_system,
             get_processing_progress,
             cleanup_temp_file,
@@ -58,6 +67,7 @@ fn main() {
             load_enhanced_conversion_map,
             save_enhanced_conversion_map,
             convert_headers_enhanced,
+            preview_header_mapping,
             apply_field_transformations,
             validate_field_values,
             extract_api_data,
@@ -71,6 +81,14 @@ fn main() {
             get_available_transformations,
             create_default_field_definition
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|_app_handle, event| {
+            // Remove uploaded workbook copies when the app shuts down
+            if let tauri::RunEvent::Exit = event {
+                if let Err(e) = utils::temp_file_manager::TempFileManager::global().cleanup_all() {
+                    log::warn!("Failed to clean up temporary files: {}", e);
+                }
+            }
+        });
 }
\ No newline at end of file
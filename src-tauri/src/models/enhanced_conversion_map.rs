@@ -5,12 +5,71 @@ use std::collections::HashMap;
 pub struct EnhancedConversionMap {
     pub version: String,
     pub header_row: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header_row_span: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header_detection: Option<HeaderDetectionConfig>,
     pub field_definitions: HashMap<String, FieldDefinition>,
     pub transformation_rules: HashMap<String, TransformationRule>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blueprint_sources: Vec<BlueprintSource>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port_range_expansion: Option<PortRangeExpansion>,
     pub created_at: Option<String>,
     pub updated_at: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeaderDetectionConfig {
+    pub mode: HeaderDetectionMode,
+    #[serde(default = "default_header_scan_rows")]
+    pub max_rows: u32,
+}
+
+/// Where a row's blueprint comes from; sources are tried in order and the first non-empty value wins
+/// 
+/// Without any configured source the column mapped to the `blueprint` field is used.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BlueprintSource {
+    /// The row's value in the column mapped to the `blueprint` field
+    Column,
+    /// A fixed cell of the sheet, e.g. "B1"
+    Cell { cell: String },
+    /// The sheet name matched against a regex; the first capture group is used when present
+    SheetName { pattern: String },
+    /// A fixed blueprint name
+    Default { value: String },
+}
+
+/// Expand interface ranges such as "et-0/0/[10-13]" or "eth[0-3]" into one row per member
+/// 
+/// Members of the listed fields are paired positionally, so every non-empty field of a row
+/// must have the same member count; other fields are copied to each row.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortRangeExpansion {
+    #[serde(default = "default_port_range_fields")]
+    pub fields: Vec<String>,
+}
+
+fn default_port_range_fields() -> Vec<String> {
+    vec!["switch_ifname".to_string(), "server_ifname".to_string()]
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HeaderDetectionMode {
+    /// Always use `header_row`
+    #[default]
+    Fixed,
+    /// Score the first `max_rows` rows against the xlsx mappings and use the best match
+    Auto,
+}
+
+fn default_header_scan_rows() -> u32 {
+    10
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FieldDefinition {
     pub display_name: String,
@@ -23,6 +82,144 @@ pub struct FieldDefinition {
     pub validation_rules: ValidationRules,
     pub ui_config: Option<UiConfig>,
     pub transformations: Option<Vec<String>>,
+    #[serde(default)]
+    pub merge_behavior: MergeBehavior,
+    #[serde(default)]
+    pub cell_coercion: CellCoercion,
+    /// How the items of an `array` field are split, cleaned and joined
+    #[serde(default)]
+    pub array_format: ArrayFormat,
+}
+
+/// How empty spreadsheet cells in a field's column are filled from neighbouring cells
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeBehavior {
+    /// Fill empty cells down from the nearest non-empty value above, merged or not
+    Always,
+    /// Never fill; only the top-left cell of a merged region carries the value
+    Never,
+    /// Fill only cells covered by an Excel merged region
+    #[default]
+    WhenMerged,
+}
+
+/// How a field's typed spreadsheet cells are turned into text before transformations run
+/// 
+/// Cells that cannot be coerced are left empty and reported in the parse report.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CellCoercion {
+    #[serde(default)]
+    pub numbers: NumberCoercion,
+    #[serde(default)]
+    pub dates: DateCoercion,
+    /// Error cells such as #N/A (or their text in CSV exports) are rejected instead of used as values
+    #[serde(default = "default_reject_errors")]
+    pub reject_errors: bool,
+}
+
+impl Default for CellCoercion {
+    fn default() -> Self {
+        Self {
+            numbers: NumberCoercion::default(),
+            dates: DateCoercion::default(),
+            reject_errors: default_reject_errors(),
+        }
+    }
+}
+
+fn default_reject_errors() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NumberCoercion {
+    /// Numbers as stored, e.g. 25 or 25.5
+    #[default]
+    Auto,
+    /// Whole numbers only; floating point noise like 24.999999999 is rounded, fractions are rejected
+    Integer,
+}
+
+/// How date cells are written back as text, e.g. to recover a port "1/1" that Excel turned into a date
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DateCoercion {
+    /// The Excel date serial number, e.g. 45292
+    #[default]
+    Serial,
+    /// ISO 8601 date, with the time when it is not midnight
+    Iso,
+    /// Month and day without padding, e.g. "1/1" (US locale input)
+    MonthDay,
+    /// Day and month without padding, e.g. "1/1" (European locale input)
+    DayMonth,
+}
+
+/// Items of a delimited list value such as "prod, rack-a\nweb"
+/// 
+/// Items are split on any of the delimiters, and on the join separator so that joined values split
+/// back into the same items. Empty items are always dropped.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArrayFormat {
+    pub delimiters: Vec<String>,
+    /// Trim whitespace around every item
+    pub trim: bool,
+    /// Keep only the first of equal items
+    pub dedupe: bool,
+    /// Sort items, so the order they were written in does not matter
+    pub sort: bool,
+    /// Separator used when the items are written back as text
+    pub join: String,
+}
+
+impl Default for ArrayFormat {
+    fn default() -> Self {
+        Self {
+            delimiters: vec![",".to_string(), "\n".to_string()],
+            trim: true,
+            dedupe: true,
+            sort: false,
+            join: ", ".to_string(),
+        }
+    }
+}
+
+impl ArrayFormat {
+    pub fn split(&self, value: &str) -> Vec<String> {
+        let mut items = vec![value.to_string()];
+        let delimiters = self.delimiters.iter().map(String::as_str).chain([self.join.trim()]);
+        for delimiter in delimiters.filter(|delimiter| !delimiter.is_empty()) {
+            items = items.iter()
+                .flat_map(|item| item.split(delimiter))
+                .map(str::to_string)
+                .collect();
+        }
+
+        let mut items: Vec<String> = items.into_iter()
+            .map(|item| if self.trim { item.trim().to_string() } else { item })
+            .filter(|item| !item.trim().is_empty())
+            .collect();
+        if self.dedupe {
+            let mut seen = std::collections::HashSet::new();
+            items.retain(|item| seen.insert(item.clone()));
+        }
+        if self.sort {
+            items.sort();
+        }
+        items
+    }
+
+    pub fn join(&self, items: &[String]) -> String {
+        items.join(&self.join)
+    }
+
+    /// Split and rejoin a value, e.g. " web,prod ,web" to "prod, web" when sorting
+    pub fn normalize(&self, value: &str) -> String {
+        self.join(&self.split(value))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +229,25 @@ pub struct XlsxMapping {
     pub priority: u32,
     pub case_sensitive: bool,
     pub transform: Option<String>,
+    /// Similarity metric of a fuzzy mapping
+    #[serde(default)]
+    pub similarity: SimilarityMetric,
+    /// Lowest confidence that counts as a match; fuzzy mappings default to 0.6
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_confidence: Option<f64>,
+}
+
+/// How a fuzzy mapping scores the similarity of a header to its pattern
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimilarityMetric {
+    /// Character edit distance scaled by length
+    #[default]
+    Levenshtein,
+    /// Edit similarity that favours a shared prefix, suited to abbreviations
+    JaroWinkler,
+    /// Word-based: ignores word order and rewards shared words, e.g. "Srv NIC Port"
+    TokenSet,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -118,6 +334,80 @@ pub enum TransformationLogic {
     Function { name: String },
     #[serde(rename = "pipeline")]
     Pipeline { steps: Vec<TransformationStep> },
+    /// if/elif/else: the logic of the first branch whose condition holds, else `else` (or the input unchanged)
+    #[serde(rename = "conditional")]
+    Conditional {
+        branches: Vec<ConditionalBranch>,
+        #[serde(rename = "else", default, skip_serializing_if = "Option::is_none")]
+        otherwise: Option<Box<TransformationLogic>>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConditionalBranch {
+    #[serde(rename = "if")]
+    pub condition: Condition,
+    pub then: TransformationLogic,
+}
+
+/// A condition on the input value or a context field
+/// 
+/// `field` names a field of the row; without it the condition tests the value being transformed.
+/// A field missing from the row is treated as empty. Values are trimmed, except for `matches`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Condition {
+    Equals {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        field: Option<String>,
+        value: String,
+        #[serde(default)]
+        case_sensitive: bool,
+    },
+    /// The value matches a regex (unanchored unless the pattern anchors it)
+    Matches {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        field: Option<String>,
+        pattern: String,
+    },
+    InSet {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        field: Option<String>,
+        values: Vec<String>,
+        #[serde(default)]
+        case_sensitive: bool,
+    },
+    /// The value is empty or whitespace only
+    Empty {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        field: Option<String>,
+    },
+    /// The value reads as yes/true/y/1, like the is_external column
+    Truthy {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        field: Option<String>,
+    },
+    /// The value is a number that compares to `value`; non-numeric values never match
+    Compare {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        field: Option<String>,
+        op: CompareOp,
+        value: f64,
+    },
+    And { conditions: Vec<Condition> },
+    Or { conditions: Vec<Condition> },
+    Not { condition: Box<Condition> },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -133,6 +423,79 @@ pub struct HeaderConversionResult {
     pub applied_transformations: HashMap<String, String>,
     pub validation_errors: Vec<ValidationError>,
     pub mapping_confidence: HashMap<String, f64>,
+    #[serde(default)]
+    pub conflicts: Vec<HeaderMappingConflict>,
+    #[serde(default)]
+    pub ambiguous_headers: Vec<AmbiguousHeader>,
+}
+
+/// A header that matched a field already assigned to a better ranked header
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HeaderMappingConflict {
+    pub field_name: String,
+    pub assigned_header: String,
+    pub rejected_header: String,
+    /// Same confidence and priority; the leftmost header won
+    pub tie: bool,
+}
+
+/// A header that matched several fields with the same confidence and priority
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AmbiguousHeader {
+    pub header: String,
+    pub assigned_field: String,
+    /// The other equally ranked fields
+    pub tied_fields: Vec<String>,
+}
+
+/// Ranked field candidates for every header, used to suggest fixes to a map before converting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeaderMappingPreview {
+    /// In header order
+    pub headers: Vec<HeaderMappingSuggestion>,
+    /// Fields no header is mapped to
+    pub unmatched_fields: Vec<String>,
+    /// Required fields no header is mapped to
+    pub missing_required_fields: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeaderMappingSuggestion {
+    pub header: String,
+    /// The field convert_headers_with_enhanced_map picks
+    pub mapped_field: Option<String>,
+    /// Up to 3, best first
+    pub candidates: Vec<HeaderMatchCandidate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeaderMatchCandidate {
+    pub field_name: String,
+    pub confidence: f64,
+    /// The xlsx mapping that matched; None for a name similarity suggestion
+    pub matched_pattern: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeaderRowDetection {
+    /// 1-based sheet row of the detected header, as used by `header_row`
+    pub header_row: u32,
+    /// Mean match confidence of the matched fields, scaled by the share of required fields matched
+    pub confidence: f64,
+    pub matched_fields: Vec<String>,
+    pub missing_required_fields: Vec<String>,
+    /// Every candidate row, in row order
+    pub candidates: Vec<HeaderRowCandidate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeaderRowCandidate {
+    /// 1-based sheet row of the candidate
+    pub header_row: u32,
+    /// Sum of the best match confidence of each matched field; the highest score wins
+    pub score: f64,
+    pub confidence: f64,
+    pub matched_field_count: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -203,8 +566,12 @@ impl EnhancedConversionMap {
         Self {
             version: "1.0.0".to_string(),
             header_row: Some(2),
+            header_row_span: None,
+            header_detection: None,
             field_definitions: HashMap::new(),
             transformation_rules: HashMap::new(),
+            blueprint_sources: Vec::new(),
+            port_range_expansion: None,
             created_at: Some(chrono::Utc::now().to_rfc3339()),
             updated_at: Some(chrono::Utc::now().to_rfc3339()),
         }
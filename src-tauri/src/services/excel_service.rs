@@ -1,25 +1,3 @@
-use crate::models::network_config::NetworkConfigRow;
-
-pub struct ExcelService;
-
-impl Default for ExcelService {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl ExcelService {
-    pub fn new() -> Self {
-        Self
-    }
-
-    pub fn get_sheet_names(&self, _file_path: &str) -> Result<Vec<String>, String> {
-        // TODO: Implement Excel sheet enumeration
-        Ok(vec!["Sheet1".to_string()])
-    }
-
-    pub fn parse_sheet(&self, _file_path: &str, _sheet_name: &str) -> Result<Vec<NetworkConfigRow>, String> {
-        // TODO: Implement Excel sheet parsing
-        Ok(vec![])
-    }
-}
\ No newline at end of file
+// The Excel service lives in the excel domain; re-exported here for backward compatibility
+#[allow(unused_imports)]
+pub use crate::domains::excel::services::ExcelProcessingService as ExcelService;
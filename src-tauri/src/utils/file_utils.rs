@@ -1,24 +1,20 @@
 use std::path::Path;
 
+/// Spreadsheet extensions readable through calamine's auto-detecting reader
+pub const SUPPORTED_SPREADSHEET_EXTENSIONS: &[&str] = &["xlsx", "xlsm", "xls", "xlsb", "ods"];
+
+/// Delimited text extensions read by the CSV parser
+pub const DELIMITED_TEXT_EXTENSIONS: &[&str] = &["csv", "tsv"];
+
 pub struct FileUtils;
 
 impl FileUtils {
-    pub fn create_temp_file(original_name: &str) -> Result<String, String> {
-        // TODO: Implement secure temporary file creation
-        Ok(format!("temp_{}", original_name))
-    }
-
-    pub fn cleanup_temp_file(temp_path: &str) -> Result<(), String> {
-        // TODO: Implement temporary file cleanup
-        if Path::new(temp_path).exists() {
-            std::fs::remove_file(temp_path).map_err(|e| e.to_string())?;
-        }
-        Ok(())
+    pub fn validate_file_type(file_path: &str) -> bool {
+        has_extension_in(file_path, SUPPORTED_SPREADSHEET_EXTENSIONS)
     }
 
-    pub fn validate_file_type(file_path: &str) -> bool {
-        // TODO: Implement file type validation for .xlsx files
-        file_path.ends_with(".xlsx")
+    pub fn is_delimited_text(file_path: &str) -> bool {
+        has_extension_in(file_path, DELIMITED_TEXT_EXTENSIONS)
     }
 
     pub fn get_file_size(file_path: &str) -> Result<u64, String> {
@@ -27,4 +23,12 @@ impl FileUtils {
             .map(|metadata| metadata.len())
             .map_err(|e| e.to_string())
     }
-}
\ No newline at end of file
+}
+
+fn has_extension_in(file_path: &str, extensions: &[&str]) -> bool {
+    Path::new(file_path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extensions.contains(&extension.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
@@ -0,0 +1,224 @@
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Largest file accepted as an upload
+pub const MAX_UPLOAD_SIZE: u64 = 50 * 1024 * 1024;
+
+lazy_static::lazy_static! {
+    static ref TEMP_FILES: TempFileManager = TempFileManager::new(
+        std::env::temp_dir().join(format!("ck-apstra-tauri-{}", uuid::Uuid::new_v4().simple())),
+        MAX_UPLOAD_SIZE
+    );
+}
+
+/// An uploaded file copied into the private temp directory
+#[derive(Debug, Clone)]
+pub struct TempFileEntry {
+    pub file_id: String,
+    pub original_name: String,
+    pub size: u64,
+    pub sha256: String,
+    pub path: PathBuf,
+}
+
+/// Registry of uploaded files, each copied into a private temp directory under an opaque id
+///
+/// Later commands refer to the id, so only files that went through `register` are read back.
+/// The copy is read once per command and its content hash checked before the bytes are handed
+/// out, so files changed on disk are caught and the path is never reopened after the check.
+pub struct TempFileManager {
+    root: PathBuf,
+    max_file_size: u64,
+    files: Mutex<HashMap<String, TempFileEntry>>,
+}
+
+impl TempFileManager {
+    pub fn new(root: PathBuf, max_file_size: u64) -> Self {
+        Self {
+            root,
+            max_file_size,
+            files: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The process-wide manager used by the commands
+    pub fn global() -> &'static TempFileManager {
+        &TEMP_FILES
+    }
+
+    /// Copy a file into the temp directory and return its registry entry
+    pub fn register(&self, source_path: &str) -> Result<TempFileEntry, String> {
+        let source = Path::new(source_path);
+        let size = fs::metadata(source)
+            .map_err(|e| format!("Failed to read file {}: {}", source_path, e))?
+            .len();
+        if size > self.max_file_size {
+            return Err(format!("File is {} bytes, larger than the {} byte upload limit", size, self.max_file_size));
+        }
+        
+        let content = fs::read(source)
+            .map_err(|e| format!("Failed to read file {}: {}", source_path, e))?;
+        if content.len() as u64 > self.max_file_size {
+            return Err(format!("File is larger than the {} byte upload limit", self.max_file_size));
+        }
+        
+        self.create_root()?;
+        let file_id = uuid::Uuid::new_v4().to_string();
+        // Keep the extension, the workbook reader detects the format from it
+        let file_name = match source.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) => format!("{}.{}", file_id, extension.to_lowercase()),
+            None => file_id.clone(),
+        };
+        let path = self.root.join(file_name);
+        write_private_file(&path, &content)?;
+        
+        let entry = TempFileEntry {
+            file_id: file_id.clone(),
+            original_name: source.file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            size: content.len() as u64,
+            sha256: sha256_hex(&content),
+            path,
+        };
+        log::info!("Registered upload {} as {} ({} bytes, sha256 {})", entry.original_name, file_id, entry.size, entry.sha256);
+        
+        self.lock_files()?.insert(file_id, entry.clone());
+        Ok(entry)
+    }
+
+    /// Content of a registered file, after checking that it is unchanged since upload
+    pub fn read(&self, file_id: &str) -> Result<(TempFileEntry, Vec<u8>), String> {
+        let entry = self.lock_files()?
+            .get(file_id)
+            .cloned()
+            .ok_or_else(|| format!("Unknown or expired file id: {}", file_id))?;
+        
+        let content = fs::read(&entry.path)
+            .map_err(|e| format!("Failed to read uploaded file {}: {}", entry.original_name, e))?;
+        if sha256_hex(&content) != entry.sha256 {
+            return Err(format!("Uploaded file {} was modified after upload", entry.original_name));
+        }
+        Ok((entry, content))
+    }
+
+    /// Delete a registered file; returns false when the id is unknown
+    pub fn remove(&self, file_id: &str) -> Result<bool, String> {
+        let Some(entry) = self.lock_files()?.remove(file_id) else {
+            return Ok(false);
+        };
+        remove_if_exists(&entry.path)?;
+        log::info!("Removed upload {} ({})", file_id, entry.original_name);
+        Ok(true)
+    }
+
+    /// Delete every registered file and the temp directory itself
+    pub fn cleanup_all(&self) -> Result<(), String> {
+        self.lock_files()?.clear();
+        if self.root.exists() {
+            fs::remove_dir_all(&self.root)
+                .map_err(|e| format!("Failed to remove temp directory {}: {}", self.root.display(), e))?;
+        }
+        Ok(())
+    }
+
+    fn create_root(&self) -> Result<(), String> {
+        let mut builder = fs::DirBuilder::new();
+        builder.recursive(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::DirBuilderExt;
+            builder.mode(0o700);
+        }
+        builder.create(&self.root)
+            .map_err(|e| format!("Failed to create temp directory {}: {}", self.root.display(), e))
+    }
+
+    fn lock_files(&self) -> Result<std::sync::MutexGuard<'_, HashMap<String, TempFileEntry>>, String> {
+        self.files.lock().map_err(|_| "Failed to acquire temp file registry lock".to_string())
+    }
+}
+
+/// Read a command's file argument, which must be the id of a file registered by `upload_excel_file`
+/// 
+/// Filesystem paths are rejected, so commands only ever read the private copies.
+pub fn read_uploaded_file(file_id: &str) -> Result<(TempFileEntry, Vec<u8>), String> {
+    TempFileManager::global().read(file_id)
+}
+
+fn write_private_file(path: &Path, content: &[u8]) -> Result<(), String> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)
+        .map_err(|e| format!("Failed to create temp file {}: {}", path.display(), e))?;
+    file.write_all(content)
+        .map_err(|e| format!("Failed to write temp file {}: {}", path.display(), e))
+}
+
+fn remove_if_exists(path: &Path) -> Result<(), String> {
+    if path.exists() {
+        fs::remove_file(path).map_err(|e| format!("Failed to remove temp file {}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+fn sha256_hex(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_temp_file_manager_registers_resolves_and_cleans_up() {
+        let root = std::env::temp_dir().join(format!("temp_file_manager_test_{}", uuid::Uuid::new_v4()));
+        let source = std::env::temp_dir().join(format!("upload_source_{}.xlsx", uuid::Uuid::new_v4()));
+        std::fs::write(&source, b"workbook bytes").unwrap();
+        let manager = TempFileManager::new(root.clone(), 64);
+
+        let entry = manager.register(&source.to_string_lossy()).unwrap();
+        std::fs::remove_file(&source).unwrap();
+        assert_eq!(entry.size, 14);
+        assert_eq!(entry.sha256.len(), 64);
+        assert!(entry.path.starts_with(&root));
+        assert!(entry.path.to_string_lossy().ends_with(".xlsx"));
+
+        // The copy stays readable after the source is gone, and tampering is detected
+        let (read_entry, content) = manager.read(&entry.file_id).unwrap();
+        assert_eq!(read_entry.path, entry.path);
+        assert_eq!(content, b"workbook bytes");
+        std::fs::write(&entry.path, b"changed").unwrap();
+        assert!(manager.read(&entry.file_id).unwrap_err().contains("modified after upload"));
+
+        assert!(manager.remove(&entry.file_id).unwrap());
+        assert!(!entry.path.exists());
+        assert!(!manager.remove(&entry.file_id).unwrap());
+        assert!(manager.read(&entry.file_id).unwrap_err().contains("Unknown or expired file id"));
+
+        manager.cleanup_all().unwrap();
+        assert!(!root.exists());
+    }
+
+    #[test]
+    fn test_temp_file_manager_enforces_size_limit() {
+        let root = std::env::temp_dir().join(format!("temp_file_manager_test_{}", uuid::Uuid::new_v4()));
+        let source = std::env::temp_dir().join(format!("upload_source_{}.xlsx", uuid::Uuid::new_v4()));
+        std::fs::write(&source, vec![0u8; 65]).unwrap();
+
+        let result = TempFileManager::new(root.clone(), 64).register(&source.to_string_lossy());
+        std::fs::remove_file(&source).unwrap();
+
+        assert!(result.unwrap_err().contains("upload limit"));
+        assert!(!root.exists());
+    }
+}
@@ -0,0 +1,121 @@
+use ck_apstra_tauri::domains::excel::commands::{
+    cleanup_temp_file, decode_text, parse_csv_file, parse_csv_file_with_report, read_delimited_records, sniff_delimiter,
+    upload_excel_file
+};
+use ck_apstra_tauri::domains::shared::models::api_contracts::RowParseStatus;
+use std::path::PathBuf;
+
+fn write_temp_file(extension: &str, contents: &[u8]) -> PathBuf {
+    let file_path = std::env::temp_dir().join(format!("csv_parsing_test_{}.{}", uuid::Uuid::new_v4(), extension));
+    std::fs::write(&file_path, contents).expect("Should write temp file");
+    file_path
+}
+
+#[test]
+fn test_sniff_delimiter() {
+    assert_eq!(sniff_delimiter("Switch,Port,Comment\nleaf-01,1,\"a; b\"\n"), b',');
+    assert_eq!(sniff_delimiter("Switch\tPort\tComment\nleaf-01\t1\t\"x, y, z\"\n"), b'\t');
+    assert_eq!(sniff_delimiter("Switch;Port;Comment\nleaf-01;1;\"1,5m cable\"\n"), b';');
+    assert_eq!(sniff_delimiter("single column\nvalue\n"), b',');
+}
+
+#[test]
+fn test_read_delimited_records_keeps_quoted_multiline_cells() {
+    let worksheet = read_delimited_records("Switch,Comment\nleaf-01,\"first line\nsecond line\"\nleaf-02,short\n", b',')
+        .expect("Should read records");
+
+    assert_eq!(worksheet.get_size(), (3, 2));
+    assert_eq!(worksheet.get((1, 1)).map(|cell| cell.to_string()), Some("first line\nsecond line".to_string()));
+    assert_eq!(worksheet.get((2, 0)).map(|cell| cell.to_string()), Some("leaf-02".to_string()));
+}
+
+#[test]
+fn test_decode_text_handles_bom_and_legacy_encodings() {
+    let utf16_bytes: Vec<u8> = [0xFF, 0xFE].into_iter()
+        .chain("Host Name,サーバー".encode_utf16().flat_map(|unit| unit.to_le_bytes()))
+        .collect();
+    assert_eq!(decode_text(&utf16_bytes, None).unwrap(), "Host Name,サーバー");
+
+    assert_eq!(decode_text(b"\xEF\xBB\xBFSwitch", None).unwrap(), "Switch");
+    assert_eq!(decode_text(b"Caf\xE9", None).unwrap(), "Café");
+
+    let shift_jis_bytes = b"\x83\x54\x81\x5B\x83\x6F\x81\x5B";
+    assert_eq!(decode_text(shift_jis_bytes, Some("shift_jis")).unwrap(), "サーバー");
+    assert!(decode_text(b"abc", Some("not-an-encoding")).is_err());
+}
+
+#[tokio::test]
+async fn test_parse_csv_file_with_enhanced_conversion_map() {
+    let contents = "Switch Name,Port,Host Name,Slot/Port,Speed,Comment\n\
+                    leaf-01,xe-0/0/1,server-01,eth0,25GB,\"rack A\nrow 3\"\n\
+                    leaf-01,xe-0/0/2,server-02,eth0,25 Gbps,\n\
+                    ,,,,,\n";
+    let file_path = write_temp_file("csv", contents.as_bytes());
+
+    let upload = upload_excel_file(file_path.to_string_lossy().to_string()).await;
+    std::fs::remove_file(&file_path).unwrap();
+    let upload = upload.expect("Should upload CSV file");
+    let file_name = file_path.file_name().unwrap().to_string_lossy().to_string();
+    assert_eq!(upload.sheet_names, vec![file_name.clone()]);
+
+    let result = parse_csv_file(upload.file_id.clone(), None, Some(1), None, None).await;
+    cleanup_temp_file(upload.file_id).await.unwrap();
+
+    let rows = result.expect("Should parse CSV file");
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].switch_label.as_deref(), Some("leaf-01"));
+    assert_eq!(rows[0].server_ifname.as_deref(), Some("eth0"));
+    assert_eq!(rows[0].link_speed.as_deref(), Some("25G"));
+    assert_eq!(rows[0].comment.as_deref(), Some("rack A\nrow 3"));
+    assert_eq!(rows[1].link_speed.as_deref(), Some("25G"));
+
+    let source = rows[1].source.as_ref().expect("Row should record its source");
+    assert_eq!(source.sheet_name, file_name);
+    assert_eq!(source.row, 3); // Record number as shown by spreadsheet apps, not the text line
+    assert_eq!(source.cell_reference("link_speed"), Some(format!("'{}'!E3", file_name)));
+}
+
+#[tokio::test]
+async fn test_parse_tsv_file_uses_tab_delimiter() {
+    let contents = "Switch Name\tPort\tHost Name\tSlot/Port\nleaf-02\txe-0/0/9\tserver-09\teth1\n";
+    let file_path = write_temp_file("tsv", contents.as_bytes());
+
+    // Only uploaded files are read, never a path
+    let by_path = parse_csv_file(file_path.to_string_lossy().to_string(), None, Some(1), None, None).await;
+    assert!(by_path.unwrap_err().contains("Unknown or expired file id"));
+
+    let upload = upload_excel_file(file_path.to_string_lossy().to_string()).await;
+    std::fs::remove_file(&file_path).unwrap();
+    let upload = upload.expect("Should upload TSV file");
+
+    let result = parse_csv_file(upload.file_id.clone(), None, Some(1), None, None).await;
+    cleanup_temp_file(upload.file_id).await.unwrap();
+
+    let rows = result.expect("Should parse TSV file");
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].switch_ifname.as_deref(), Some("xe-0/0/9"));
+    assert_eq!(rows[0].server_label.as_deref(), Some("server-09"));
+}
+
+#[tokio::test]
+async fn test_parse_csv_file_with_report_lists_skipped_records() {
+    let contents = "Switch Name,Port,Host Name,Slot/Port\n\
+                    leaf-01,xe-0/0/1,server-01,eth0\n\
+                    leaf-01,,server-02,\n";
+    let file_path = write_temp_file("csv", contents.as_bytes());
+
+    let upload = upload_excel_file(file_path.to_string_lossy().to_string()).await;
+    std::fs::remove_file(&file_path).unwrap();
+    let upload = upload.expect("Should upload CSV file");
+
+    let result = parse_csv_file_with_report(upload.file_id.clone(), None, Some(1), None, None, None).await;
+    cleanup_temp_file(upload.file_id).await.unwrap();
+
+    let parsed_data = result.expect("Should parse CSV file");
+    let report = &parsed_data.report;
+    assert_eq!(parsed_data.rows.len(), 1);
+    assert_eq!((report.total_rows, report.parsed_rows, report.skipped_rows), (2, 1, 1));
+    assert_eq!(report.row_issues[0].status, RowParseStatus::Skipped);
+    assert_eq!(report.row_issues[0].error.row_index, 3);
+    assert_eq!(report.row_issues[0].missing_required_fields, vec!["server_ifname", "switch_ifname"]);
+}
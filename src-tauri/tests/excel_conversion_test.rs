@@ -1,7 +1,7 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::collections::HashMap;
-use ck_apstra_tauri::models::enhanced_conversion_map::EnhancedConversionMap;
-use ck_apstra_tauri::domains::excel::commands::parse_excel_sheet;
+use ck_apstra_tauri::models::enhanced_conversion_map::{BlueprintSource, EnhancedConversionMap};
+use ck_apstra_tauri::domains::excel::commands::{cleanup_temp_file, parse_csv_file, parse_excel_sheet, upload_excel_file};
 
 /// Integration tests for Excel conversion functionality using real Excel fixtures
 #[cfg(test)]
@@ -17,6 +17,13 @@ mod excel_integration_tests {
             .join("original-0729.xlsx")
     }
 
+    /// Register a fixture the way the frontend does; commands only read uploaded file ids
+    async fn upload_fixture(fixture_path: &Path) -> String {
+        upload_excel_file(fixture_path.to_string_lossy().to_string()).await
+            .expect("Should upload fixture")
+            .file_id
+    }
+
     fn create_enhanced_conversion_map_for_test() -> EnhancedConversionMap {
         // Use the actual default enhanced conversion map
         use ck_apstra_tauri::domains::conversion::services::enhanced_conversion_service::EnhancedConversionService;
@@ -34,16 +41,17 @@ mod excel_integration_tests {
         }
 
         let conversion_map = create_enhanced_conversion_map_for_test();
-        let file_path = fixture_path.to_string_lossy().to_string();
+        let file_id = upload_fixture(&fixture_path).await;
 
         // Test parsing different sheets that are known to exist in the fixture
         let test_sheets = vec!["4187-11", "4187-12"];
         
         for sheet_name in test_sheets {
             let result = parse_excel_sheet(
-                file_path.clone(),
+                file_id.clone(),
                 sheet_name.to_string(),
-                Some(conversion_map.clone())
+                Some(conversion_map.clone()),
+                None
             ).await;
 
             match result {
@@ -249,12 +257,13 @@ mod excel_integration_tests {
         }
 
         // Test with field variations instead of exact conversion map
-        let file_path = fixture_path.to_string_lossy().to_string();
+        let file_id = upload_fixture(&fixture_path).await;
         
         let result = parse_excel_sheet(
-            file_path,
+            file_id,
             "4187-11".to_string(),
-            None // Use default field variations
+            None, // Use default field variations
+            None
         ).await;
 
         match result {
@@ -286,12 +295,13 @@ mod excel_integration_tests {
         }
 
         let conversion_map = create_enhanced_conversion_map_for_test();
-        let file_path = fixture_path.to_string_lossy().to_string();
+        let file_id = upload_fixture(&fixture_path).await;
 
         if let Ok(parsed_data) = parse_excel_sheet(
-            file_path,
+            file_id,
             "4187-11".to_string(),
-            Some(conversion_map)
+            Some(conversion_map),
+            None
         ).await {
             // Test successful parsing
             println!("Successfully parsed {} rows in validation test", parsed_data.len());
@@ -312,12 +322,13 @@ mod excel_integration_tests {
         
         // The enhanced conversion map already handles header variations through field definitions
         
-        let file_path = fixture_path.to_string_lossy().to_string();
+        let file_id = upload_fixture(&fixture_path).await;
 
         let result = parse_excel_sheet(
-            file_path,
+            file_id,
             "4187-11".to_string(),
-            Some(conversion_map)
+            Some(conversion_map),
+            None
         ).await;
 
         match result {
@@ -352,12 +363,13 @@ mod excel_integration_tests {
         }
 
         let conversion_map = create_enhanced_conversion_map_for_test();
-        let file_path = fixture_path.to_string_lossy().to_string();
+        let file_id = upload_fixture(&fixture_path).await;
 
         if let Ok(parsed_data) = parse_excel_sheet(
-            file_path,
+            file_id,
             "4187-11".to_string(),
-            Some(conversion_map)
+            Some(conversion_map),
+            None
         ).await {
             // Check that empty and whitespace-only values are properly handled
             for (i, row) in parsed_data.iter().take(10).enumerate() {
@@ -383,7 +395,7 @@ mod excel_integration_tests {
     }
 
     #[tokio::test]
-    async fn test_blueprint_always_none() {
+    async fn test_blueprint_from_sheet_name_source() {
         let fixture_path = get_test_fixture_path();
         
         if !fixture_path.exists() {
@@ -391,22 +403,57 @@ mod excel_integration_tests {
             return;
         }
 
-        // Use the enhanced conversion map (blueprint field was removed from it)
-        let conversion_map = create_enhanced_conversion_map_for_test();
+        // The default map has no blueprint column, so the sheet name source fills every row
+        let mut conversion_map = create_enhanced_conversion_map_for_test();
+        conversion_map.blueprint_sources = vec![
+            BlueprintSource::Column,
+            BlueprintSource::SheetName { pattern: r"^(\d+)-\d+$".to_string() },
+        ];
         
-        let file_path = fixture_path.to_string_lossy().to_string();
+        let file_id = upload_fixture(&fixture_path).await;
 
-        if let Ok(parsed_data) = parse_excel_sheet(
-            file_path,
+        let parsed_data = parse_excel_sheet(
+            file_id,
             "4187-11".to_string(),
-            Some(conversion_map)
-        ).await {
-            // Verify that ALL rows have blueprint set to None, regardless of Excel data
-            for row in &parsed_data {
-                assert_eq!(row.blueprint, None, "Blueprint must always be None");
-            }
-            
-            println!("Blueprint requirement test passed - all {} rows have blueprint=None", parsed_data.len());
+            Some(conversion_map),
+            None
+        ).await.expect("Should parse fixture sheet");
+
+        assert!(!parsed_data.is_empty(), "Should parse rows from the fixture");
+        for row in &parsed_data {
+            assert_eq!(row.blueprint.as_deref(), Some("4187"), "Blueprint should come from the sheet name");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_blueprint_from_cell_source() {
+        // A title line names the blueprint above the header row
+        let fixture_path = std::env::temp_dir().join(format!("blueprint_fixture_{}.csv", uuid::Uuid::new_v4()));
+        std::fs::write(&fixture_path, "Blueprint,DH4-Colo2\n\
+                                       Switch Name,Port,Host Name,Slot/Port\n\
+                                       leaf-01,xe-0/0/1,server-01,eth0\n\
+                                       leaf-01,xe-0/0/2,server-02,eth0\n").unwrap();
+        let file_id = upload_fixture(&fixture_path).await;
+        std::fs::remove_file(&fixture_path).unwrap();
+
+        let mut conversion_map = create_enhanced_conversion_map_for_test();
+        let unconfigured = parse_csv_file(file_id.clone(), Some(conversion_map.clone()), Some(2), None, None).await
+            .expect("Should parse fixture");
+        assert_eq!(unconfigured.len(), 2);
+        assert!(unconfigured.iter().all(|row| row.blueprint.is_none()), "No blueprint without a blueprint column or source");
+
+        conversion_map.blueprint_sources = vec![
+            BlueprintSource::Column,
+            BlueprintSource::Cell { cell: "B1".to_string() },
+            BlueprintSource::Default { value: "fallback".to_string() },
+        ];
+        let parsed_data = parse_csv_file(file_id.clone(), Some(conversion_map), Some(2), None, None).await
+            .expect("Should parse fixture");
+        cleanup_temp_file(file_id).await.unwrap();
+
+        assert_eq!(parsed_data.len(), 2);
+        for row in &parsed_data {
+            assert_eq!(row.blueprint.as_deref(), Some("DH4-Colo2"));
         }
     }
 
@@ -420,16 +467,17 @@ mod excel_integration_tests {
         }
 
         let conversion_map = create_enhanced_conversion_map_for_test();
-        let file_path = fixture_path.to_string_lossy().to_string();
+        let file_id = upload_fixture(&fixture_path).await;
 
         // Test both sheets mentioned in the issue
         let test_sheets = vec!["4187-11", "4187-12"];
         
         for sheet_name in test_sheets {
             let result = parse_excel_sheet(
-                file_path.clone(),
+                file_id.clone(),
                 sheet_name.to_string(),
-                Some(conversion_map.clone())
+                Some(conversion_map.clone()),
+                None
             ).await;
 
             match result {
@@ -0,0 +1,60 @@
+use ck_apstra_tauri::domains::conversion::services::enhanced_conversion_service::EnhancedConversionService;
+use ck_apstra_tauri::models::enhanced_conversion_map::{EnhancedConversionMap, MappingType, SimilarityMetric, XlsxMapping};
+
+fn fuzzy_mapping(pattern: &str, similarity: SimilarityMetric, min_confidence: Option<f64>) -> XlsxMapping {
+    XlsxMapping {
+        pattern: pattern.to_string(),
+        mapping_type: MappingType::Fuzzy,
+        priority: 60,
+        case_sensitive: false,
+        transform: None,
+        similarity,
+        min_confidence,
+    }
+}
+
+fn default_map_with(field_name: &str, xlsx_mapping: XlsxMapping) -> EnhancedConversionMap {
+    let mut enhanced_map = EnhancedConversionService::load_default_enhanced_conversion_map()
+        .expect("Should load default enhanced conversion map");
+    enhanced_map.field_definitions.get_mut(field_name)
+        .expect("Field should be defined")
+        .xlsx_mappings
+        .push(xlsx_mapping);
+    enhanced_map
+}
+
+#[test]
+fn test_token_set_mapping_matches_abbreviated_header() {
+    let service = EnhancedConversionService::new();
+    let enhanced_map = default_map_with("server_ifname", fuzzy_mapping("Server NIC Port", SimilarityMetric::TokenSet, Some(0.75)));
+
+    let excel_headers: Vec<String> = ["Switch Name", "Port", "Srv NIC Port", "Switch NIC Port"].iter().map(|h| h.to_string()).collect();
+    let conversion_result = service.convert_headers_with_enhanced_map(&excel_headers, &enhanced_map)
+        .expect("Should convert headers successfully");
+
+    assert_eq!(conversion_result.converted_headers.get("Srv NIC Port").map(String::as_str), Some("server_ifname"));
+    // Shares two of three words, but stays below the mapping's min_confidence
+    assert_ne!(conversion_result.converted_headers.get("Switch NIC Port").map(String::as_str), Some("server_ifname"));
+
+    // Word order only matters to whole-string Levenshtein
+    let reordered = vec!["NIC Port (Srv)".to_string()];
+    let token_set_result = service.convert_headers_with_enhanced_map(&reordered, &enhanced_map).unwrap();
+    assert_eq!(token_set_result.converted_headers.get("NIC Port (Srv)").map(String::as_str), Some("server_ifname"));
+    let levenshtein_map = default_map_with("server_ifname", fuzzy_mapping("Server NIC Port", SimilarityMetric::Levenshtein, None));
+    let levenshtein_result = service.convert_headers_with_enhanced_map(&reordered, &levenshtein_map).unwrap();
+    assert!(!levenshtein_result.converted_headers.contains_key("NIC Port (Srv)"));
+}
+
+#[test]
+fn test_min_confidence_controls_fuzzy_matches() {
+    let service = EnhancedConversionService::new();
+    let mut enhanced_map = default_map_with("link_speed", fuzzy_mapping("Link Speed", SimilarityMetric::JaroWinkler, None));
+    let headers = vec!["Lnk Spd".to_string()];
+
+    let matched = service.convert_headers_with_enhanced_map(&headers, &enhanced_map).unwrap();
+    assert_eq!(matched.converted_headers.get("Lnk Spd").map(String::as_str), Some("link_speed"));
+
+    enhanced_map.field_definitions.get_mut("link_speed").unwrap().xlsx_mappings.last_mut().unwrap().min_confidence = Some(0.95);
+    let rejected = service.convert_headers_with_enhanced_map(&headers, &enhanced_map).unwrap();
+    assert!(!rejected.converted_headers.contains_key("Lnk Spd"));
+}
@@ -0,0 +1,32 @@
+use ck_apstra_tauri::domains::conversion::services::enhanced_conversion_service::EnhancedConversionService;
+use ck_apstra_tauri::models::enhanced_conversion_map::HeaderMappingConflict;
+
+#[test]
+fn test_header_assignment_is_one_to_one_and_honors_priority() {
+    let service = EnhancedConversionService::new();
+    let enhanced_map = EnhancedConversionService::load_default_enhanced_conversion_map()
+        .expect("Should load default enhanced conversion map");
+
+    // "Server" (priority 90) comes first, but "Host Name" (priority 100) wins server_label
+    let excel_headers: Vec<String> = ["Server", "Host Name", "Switch Name", "Server Tags"].iter().map(|h| h.to_string()).collect();
+    let conversion_result = service.convert_headers_with_enhanced_map(&excel_headers, &enhanced_map)
+        .expect("Should convert headers successfully");
+
+    assert_eq!(conversion_result.converted_headers.get("Host Name").map(String::as_str), Some("server_label"));
+    assert_eq!(conversion_result.converted_headers.get("Switch Name").map(String::as_str), Some("switch_label"));
+    assert_eq!(conversion_result.converted_headers.get("Server Tags").map(String::as_str), Some("server_tags"));
+    assert!(!conversion_result.converted_headers.contains_key("Server"));
+    assert_eq!(conversion_result.conflicts, vec![HeaderMappingConflict {
+        field_name: "server_label".to_string(),
+        assigned_header: "Host Name".to_string(),
+        rejected_header: "Server".to_string(),
+        tie: false,
+    }]);
+    assert!(conversion_result.validation_errors.iter().any(|error| error.field == "Server" && error.message.contains("already mapped from 'Host Name'")));
+
+    // Every field is assigned at most once
+    let mut fields: Vec<&String> = conversion_result.converted_headers.values().collect();
+    fields.sort();
+    fields.dedup();
+    assert_eq!(fields.len(), conversion_result.converted_headers.len());
+}
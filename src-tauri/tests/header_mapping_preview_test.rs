@@ -0,0 +1,31 @@
+use ck_apstra_tauri::domains::conversion::services::enhanced_conversion_service::EnhancedConversionService;
+
+#[test]
+fn test_preview_header_mapping_ranks_candidates_and_flags_missing_fields() {
+    let service = EnhancedConversionService::new();
+    let enhanced_map = EnhancedConversionService::load_default_enhanced_conversion_map()
+        .expect("Default map should load");
+    let headers: Vec<String> = ["Switch Name", "Port", "Srvr Name", "Speed"].iter().map(|h| h.to_string()).collect();
+
+    let preview = service.preview_header_mapping(&headers, &enhanced_map)
+        .expect("Default map patterns should compile");
+
+    assert_eq!(preview.headers.len(), 4);
+    let switch_name = &preview.headers[0];
+    assert_eq!(switch_name.mapped_field.as_deref(), Some("switch_label"));
+    assert_eq!(switch_name.candidates[0].field_name, "switch_label");
+    assert_eq!(switch_name.candidates[0].confidence, 1.0);
+    assert_eq!(switch_name.candidates[0].matched_pattern.as_deref(), Some("Switch Name"));
+
+    // No mapping matches the abbreviation, so candidates are ranked by name similarity
+    let server_name = &preview.headers[2];
+    assert_eq!(server_name.mapped_field, None);
+    assert_eq!(server_name.candidates.len(), 3);
+    assert_eq!(server_name.candidates[0].field_name, "server_label");
+    assert!(server_name.candidates[0].matched_pattern.is_none());
+    assert!(server_name.candidates.windows(2).all(|pair| pair[0].confidence >= pair[1].confidence));
+
+    assert!(preview.unmatched_fields.contains(&"comment".to_string()));
+    assert!(!preview.unmatched_fields.contains(&"link_speed".to_string()));
+    assert_eq!(preview.missing_required_fields, vec!["server_ifname".to_string(), "server_label".to_string()]);
+}
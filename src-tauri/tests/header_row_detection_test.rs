@@ -0,0 +1,60 @@
+use ck_apstra_tauri::domains::conversion::services::enhanced_conversion_service::EnhancedConversionService;
+
+fn to_rows(rows: &[&[&str]]) -> Vec<Vec<String>> {
+    rows.iter()
+        .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+        .collect()
+}
+
+#[test]
+fn test_detect_header_row_picks_best_matching_row() {
+    let enhanced_map = EnhancedConversionService::load_default_enhanced_conversion_map()
+        .expect("Should load default enhanced conversion map");
+    let service = EnhancedConversionService::new();
+
+    let candidate_rows = to_rows(&[
+        &["Cabling plan", "", "", ""],
+        &["Speed", "", "", ""],
+        &["Switch Name", "Port", "Host Name", "Slot/Port", "Speed"],
+        &["leaf-01", "1", "server-01", "eth0", "25G"],
+    ]);
+
+    let detection = service.detect_header_row(&candidate_rows, &enhanced_map)
+        .expect("Default map patterns should compile")
+        .expect("Should detect a header row");
+
+    assert_eq!(detection.header_row, 3);
+    assert_eq!(detection.candidates.len(), 4);
+    assert!(detection.matched_fields.contains(&"switch_label".to_string()));
+    assert!(detection.matched_fields.contains(&"link_speed".to_string()));
+    assert!(detection.missing_required_fields.is_empty());
+    assert!(detection.confidence > 0.9, "Confidence should be high, got {}", detection.confidence);
+}
+
+#[test]
+fn test_detect_header_row_reports_missing_required_fields() {
+    let enhanced_map = EnhancedConversionService::load_default_enhanced_conversion_map()
+        .expect("Should load default enhanced conversion map");
+    let service = EnhancedConversionService::new();
+
+    let candidate_rows = to_rows(&[&["Switch Name", "Port", "Comment"]]);
+
+    let detection = service.detect_header_row(&candidate_rows, &enhanced_map)
+        .expect("Default map patterns should compile")
+        .expect("Should detect a header row");
+
+    assert_eq!(detection.header_row, 1);
+    assert_eq!(detection.missing_required_fields, vec!["server_ifname".to_string(), "server_label".to_string()]);
+    assert!(detection.confidence <= 0.5, "Confidence should reflect missing required fields, got {}", detection.confidence);
+}
+
+#[test]
+fn test_detect_header_row_returns_none_without_matches() {
+    let enhanced_map = EnhancedConversionService::load_default_enhanced_conversion_map()
+        .expect("Should load default enhanced conversion map");
+    let service = EnhancedConversionService::new();
+
+    let candidate_rows = to_rows(&[&["", ""], &["foo", "bar"]]);
+
+    assert!(service.detect_header_row(&candidate_rows, &enhanced_map).unwrap().is_none());
+}
@@ -1,4 +1,4 @@
-use ck_apstra_tauri::domains::excel::commands::parse_excel_sheet;
+use ck_apstra_tauri::domains::excel::commands::{parse_excel_sheet, upload_excel_file};
 use ck_apstra_tauri::domains::conversion::services::enhanced_conversion_service::EnhancedConversionService;
 use calamine::{Reader, open_workbook, Xlsx};
 
@@ -36,13 +36,19 @@ async fn test_lag_mode_with_actual_excel_file() {
     let sheet_names_to_try = vec!["4187-11", "4187-12", "Sheet1", "Sheet3", "Original", "Data", "Connections", "Network"];
     let mut successful_parse = false;
     
+    // Commands only read uploaded file ids
+    let file_id = upload_excel_file(test_file_path.to_string()).await
+        .expect("Should upload fixture")
+        .file_id;
+    
     for sheet_name in &sheet_names_to_try {
         if available_sheets.contains(&sheet_name.to_string()) {
             println!("🔍 Trying sheet: '{}'", sheet_name);
             match parse_excel_sheet(
-                test_file_path.to_string(),
+                file_id.clone(),
                 sheet_name.to_string(),
-                Some(enhanced_map.clone())
+                Some(enhanced_map.clone()),
+                None
             ).await {
                 Ok(parsed_data) => {
                     println!("✅ Successfully parsed Excel file from sheet '{}' with {} rows", sheet_name, parsed_data.len());
@@ -1,4 +1,4 @@
-use ck_apstra_tauri::domains::excel::commands::parse_excel_sheet;
+use ck_apstra_tauri::domains::excel::commands::{parse_excel_sheet, upload_excel_file};
 use ck_apstra_tauri::domains::conversion::services::enhanced_conversion_service::EnhancedConversionService;
 use calamine::{Reader, open_workbook, Xlsx};
 use std::collections::HashMap;
@@ -105,10 +105,16 @@ async fn debug_excel_processing_pipeline() {
     // Step 4: Compare with actual parse_excel_sheet result
     println!("\n📋 STEP 4: Comparing with parse_excel_sheet result...");
     
+    // Commands only read uploaded file ids
+    let file_id = upload_excel_file(test_file_path.to_string()).await
+        .expect("Should upload fixture")
+        .file_id;
+    
     match parse_excel_sheet(
-        test_file_path.to_string(),
+        file_id,
         sheet_name.to_string(),
-        Some(enhanced_map)
+        Some(enhanced_map),
+        None
     ).await {
         Ok(parsed_data) => {
             println!("✅ parse_excel_sheet returned {} rows", parsed_data.len());
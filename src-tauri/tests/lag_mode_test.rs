@@ -9,27 +9,33 @@ fn test_lag_mode_header_mapping() {
     
     let service = EnhancedConversionService::new();
     
-    // Test that "LACP\nNeeded" header maps to link_group_lag_mode field
+    // Test that "LACP\nNeeded" and "LAG Mode" headers each map to link_group_lag_mode field
+    for excel_header in ["LACP\nNeeded", "LAG Mode"] {
+        let conversion_result = service.convert_headers_with_enhanced_map(&[excel_header.to_string()], &enhanced_map)
+            .expect("Should convert headers successfully");
+        let mapped_field = conversion_result.converted_headers.get(excel_header)
+            .unwrap_or_else(|| panic!("{:?} header should map to a field", excel_header));
+        assert_eq!(mapped_field, "link_group_lag_mode", "{:?} header should map to link_group_lag_mode field", excel_header);
+    }
+    
+    // In one header row the field is assigned once; the equally ranked second header is a reported tie
     let excel_headers = vec![
         "LACP\nNeeded".to_string(),
         "LAG Mode".to_string(), 
     ];
-    
-    // Convert headers using enhanced mapping
     let conversion_result = service.convert_headers_with_enhanced_map(&excel_headers, &enhanced_map)
         .expect("Should convert headers successfully");
     
-    // Verify that both headers map to link_group_lag_mode
     let mapped_field1 = conversion_result.converted_headers.get("LACP\nNeeded")
         .expect("LACP\\nNeeded header should map to a field");
     assert_eq!(mapped_field1, "link_group_lag_mode", "LACP\\nNeeded header should map to link_group_lag_mode field");
-    
-    let mapped_field2 = conversion_result.converted_headers.get("LAG Mode")
-        .expect("LAG Mode header should map to a field");
-    assert_eq!(mapped_field2, "link_group_lag_mode", "LAG Mode header should map to link_group_lag_mode field");
+    assert!(!conversion_result.converted_headers.contains_key("LAG Mode"), "LAG Mode should not map to the same field again");
+    assert_eq!(conversion_result.conflicts.len(), 1);
+    assert_eq!(conversion_result.conflicts[0].field_name, "link_group_lag_mode");
+    assert_eq!(conversion_result.conflicts[0].rejected_header, "LAG Mode");
+    assert!(conversion_result.conflicts[0].tie, "Both exact mappings have priority 100");
     
     println!("✅ Header mapping: 'LACP\\nNeeded' -> '{}'", mapped_field1);
-    println!("✅ Header mapping: 'LAG Mode' -> '{}'", mapped_field2);
 }
 
 #[test]
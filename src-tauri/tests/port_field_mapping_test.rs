@@ -1,4 +1,4 @@
-use ck_apstra_tauri::domains::excel::commands::parse_excel_sheet;
+use ck_apstra_tauri::domains::excel::commands::{parse_excel_sheet, upload_excel_file};
 use ck_apstra_tauri::domains::conversion::services::enhanced_conversion_service::EnhancedConversionService;
 use std::path::Path;
 
@@ -16,10 +16,16 @@ async fn test_port_field_mapping_regression() {
     // Use default enhanced conversion map which has "Port" -> "switch_ifname"
     let conversion_map = EnhancedConversionService::load_default_enhanced_conversion_map().ok();
     
+    // Commands only read uploaded file ids
+    let file_id = upload_excel_file(test_file.to_string_lossy().to_string()).await
+        .expect("Should upload fixture")
+        .file_id;
+    
     let result = parse_excel_sheet(
-        test_file.to_string_lossy().to_string(),
+        file_id,
         "4187-11".to_string(),
-        conversion_map
+        conversion_map,
+        None
     ).await;
 
     match result {
@@ -0,0 +1,78 @@
+use ck_apstra_tauri::domains::excel::services::workbook_diff::diff_rows;
+use ck_apstra_tauri::domains::shared::models::api_contracts::{FieldChange, NetworkConfigRow, RowSource};
+
+fn row(switch_label: &str, switch_ifname: &str, server_label: &str, sheet_row: usize) -> NetworkConfigRow {
+    NetworkConfigRow {
+        switch_label: Some(switch_label.to_string()),
+        switch_ifname: Some(switch_ifname.to_string()),
+        server_label: Some(server_label.to_string()),
+        link_speed: Some("25G".to_string()),
+        source: Some(RowSource {
+            sheet_name: "Rack A".to_string(),
+            row: sheet_row,
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_diff_rows_reports_added_removed_and_modified_rows() {
+    let old_rows = vec![
+        row("leaf-01", "et-0/0/1", "server-01", 3),
+        row("leaf-01", "et-0/0/2", "server-02", 4),
+        row("leaf-02", "et-0/0/1", "server-03", 5),
+    ];
+    let mut moved_and_renamed = row("leaf-01", " et-0/0/2 ", "server-02b", 9);
+    moved_and_renamed.link_speed = None;
+    let new_rows = vec![
+        row("leaf-01", "et-0/0/1", "server-01", 4), // Only moved down a row
+        moved_and_renamed,
+        row("leaf-03", "et-0/0/1", "server-04", 10),
+    ];
+
+    let diff = diff_rows(&old_rows, &new_rows);
+
+    assert_eq!(diff.unchanged_count, 1);
+    assert_eq!(diff.added.len(), 1);
+    assert_eq!(diff.added[0].switch_label.as_deref(), Some("leaf-03"));
+    assert_eq!(diff.removed.len(), 1);
+    assert_eq!(diff.removed[0].switch_label.as_deref(), Some("leaf-02"));
+
+    assert_eq!(diff.modified.len(), 1);
+    let modified = &diff.modified[0];
+    assert_eq!((modified.switch_label.as_str(), modified.switch_ifname.as_str()), ("leaf-01", "et-0/0/2"));
+    assert_eq!(modified.previous_source.as_ref().map(|source| source.row), Some(4));
+    assert_eq!(modified.row.source.as_ref().map(|source| source.row), Some(9));
+    assert_eq!(modified.changes, vec![
+        FieldChange {
+            field: "server_label".to_string(),
+            old_value: Some("server-02".to_string()),
+            new_value: Some("server-02b".to_string()),
+        },
+        FieldChange {
+            field: "link_speed".to_string(),
+            old_value: Some("25G".to_string()),
+            new_value: None,
+        },
+    ]);
+}
+
+#[test]
+fn test_diff_rows_ignores_list_order() {
+    let mut old_row = row("leaf-01", "et-0/0/1", "server-01", 3);
+    old_row.server_tags = vec!["web".to_string(), "prod".to_string()];
+    old_row.link_group_ct_names = vec!["CT-PROD".to_string()];
+    let mut new_row = row("leaf-01", "et-0/0/1", "server-01", 3);
+    new_row.server_tags = vec!["prod".to_string(), "web".to_string()];
+    new_row.link_group_ct_names = vec!["CT-PROD".to_string(), "CT-MGMT".to_string()];
+
+    let diff = diff_rows(&[old_row], &[new_row]);
+
+    assert_eq!(diff.modified.len(), 1);
+    assert_eq!(diff.modified[0].changes, vec![FieldChange {
+        field: "link_group_ct_names".to_string(),
+        old_value: Some("CT-PROD".to_string()),
+        new_value: Some("CT-MGMT, CT-PROD".to_string()),
+    }]);
+}
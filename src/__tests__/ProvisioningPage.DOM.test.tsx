@@ -44,7 +44,7 @@ vi.mock('../domains/excel', () => ({
     return (
       <div data-testid="file-upload">
         <button 
-          onClick={() => onSheetsLoaded(['Sheet1', 'Sheet2'], '/test/file.xlsx')}
+          onClick={() => onSheetsLoaded(['Sheet1', 'Sheet2'], 'test-file-id', 'file.xlsx')}
         >
           Upload File
         </button>
@@ -112,12 +112,12 @@ describe('ProvisioningPage DOM Rendering Tests', () => {
       server_ifname: 'ens8',
       link_speed: '25G',
       link_group_lag_mode: 'lacp_active',
-      link_group_ct_names: 'CT1',
+      link_group_ct_names: ['CT1'],
       link_group_ifname: '',
       is_external: false,
-      server_tags: '',
+      server_tags: [],
       switch_tags: '',
-      link_tags: '',
+      link_tags: [],
       comment: ''
     },
     {
@@ -128,12 +128,12 @@ describe('ProvisioningPage DOM Rendering Tests', () => {
       server_ifname: 'ens9',
       link_speed: '25G',
       link_group_lag_mode: 'lacp_active',
-      link_group_ct_names: 'CT1',
+      link_group_ct_names: ['CT1'],
       link_group_ifname: '',
       is_external: false,
-      server_tags: '',
+      server_tags: [],
       switch_tags: '',
-      link_tags: '',
+      link_tags: [],
       comment: ''
     }
   ];
@@ -280,7 +280,7 @@ describe('ProvisioningPage DOM Rendering Tests', () => {
 
     // Verify invoke was called with correct parameters
     expect(mockInvoke).toHaveBeenCalledWith('parse_excel_sheet', {
-      filePath: '/test/file.xlsx',
+      fileId: 'test-file-id',
       sheetName: 'Sheet1',
       enhancedConversionMap: mockConversionMap
     });
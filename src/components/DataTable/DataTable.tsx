@@ -13,6 +13,7 @@ const DataTable: React.FC<DataTableProps> = ({ data, isLoading }) => {
   const renderCellValue = (value: any): string => {
     if (value === null || value === undefined) return '';
     if (typeof value === 'boolean') return value ? 'Yes' : 'No';
+    if (Array.isArray(value)) return value.join(', ');
     return String(value);
   };
 
@@ -26,7 +26,8 @@ const ProvisioningPage: React.FC<ProvisioningPageProps> = ({
   apstraConfig
 }) => {
   const [sheets, setSheets] = useState<string[]>([]);
-  const [filePath, setFilePath] = useState<string>('');
+  const [fileId, setFileId] = useState<string>('');
+  const [fileName, setFileName] = useState<string>('');
   const [selectedSheet, setSelectedSheet] = useState<string>('');
   const [tableData, setTableData] = useState<NetworkConfigRow[]>([]);
   const [isLoadingData, setIsLoadingData] = useState(false);
@@ -35,17 +36,25 @@ const ProvisioningPage: React.FC<ProvisioningPageProps> = ({
   const [blueprintValidation, setBlueprintValidation] = useState<{found: number, total: number} | null>(null);
   const [isDetectingBlueprint, setIsDetectingBlueprint] = useState(false);
 
-  const handleSheetsLoaded = (loadedSheets: string[], loadedFilePath: string) => {
+  const handleSheetsLoaded = (loadedSheets: string[], loadedFileId: string, loadedFileName?: string) => {
+    // Release the previous upload's temp copy
+    if (fileId && fileId !== loadedFileId) {
+      invoke('cleanup_temp_file', { fileId }).catch(error => {
+        console.warn('Failed to clean up previous upload:', error);
+      });
+    }
+    const displayName = loadedFileName ?? loadedFileId;
     setSheets(loadedSheets);
-    setFilePath(loadedFilePath);
+    setFileId(loadedFileId);
+    setFileName(displayName);
     setSelectedSheet(''); // Reset sheet selection
     setTableData([]); // Clear previous data
     logger.logWorkflowStep('Network Provisioning', 1, 'Excel file uploaded and sheets loaded', { 
-      fileName: loadedFilePath.split('/').pop(), 
+      fileName: displayName, 
       sheetCount: loadedSheets.length, 
       sheets: loadedSheets 
     });
-    logger.logFileOperation('Excel upload', loadedFilePath, 0, { sheets: loadedSheets });
+    logger.logFileOperation('Excel upload', displayName, 0, { sheets: loadedSheets });
   };
 
 
@@ -115,14 +124,14 @@ const ProvisioningPage: React.FC<ProvisioningPageProps> = ({
     setIsLoadingData(true);
     
     try {
-      console.log('✅ Invoking parse_excel_sheet with:', { filePath, sheetName, hasConversionMap: !!conversionMap });
+      console.log('✅ Invoking parse_excel_sheet with:', { fileId, sheetName, hasConversionMap: !!conversionMap });
       logger.logWorkflowStep('Network Provisioning', 2, 'Sheet selected and parsing data', { 
         sheetName, 
         hasConversionMap: !!conversionMap 
       });
       
       const parsedData = await invoke<NetworkConfigRow[]>('parse_excel_sheet', { 
-        filePath: filePath, 
+        fileId: fileId, 
         sheetName: sheetName,
         enhancedConversionMap: conversionMap 
       });
@@ -192,7 +201,7 @@ const ProvisioningPage: React.FC<ProvisioningPageProps> = ({
       console.error('❌ Failed to parse sheet data:', error);
       logger.logError('DATA_CHANGE', 'Excel sheet parsing failed', { 
         sheetName, 
-        filePath, 
+        fileId, 
         error: error.toString(),
         stack: error.stack
       });
@@ -295,7 +304,7 @@ const ProvisioningPage: React.FC<ProvisioningPageProps> = ({
           <div className="step-header">Step 1: Select File</div>
           <div className="step-content">
             <FileUpload onSheetsLoaded={handleSheetsLoaded} />
-            {filePath && <div className="file-selected">{filePath.split('/').pop()}</div>}
+            {fileId && <div className="file-selected">{fileName}</div>}
           </div>
         </div>
 
@@ -305,7 +314,7 @@ const ProvisioningPage: React.FC<ProvisioningPageProps> = ({
             {sheets.length > 0 ? (
               <SheetSelector 
                 sheets={sheets}
-                filePath={filePath}
+                fileId={fileId}
                 selectedSheet={selectedSheet}
                 onSheetSelect={handleSheetSelect}
               />
@@ -29,6 +29,14 @@ interface EnhancedConversionMap {
   field_definitions: Record<string, FieldDefinition>;
 }
 
+// Split a comma-separated API value into a trimmed list
+const splitList = (value: string): string[] =>
+  value.split(',').map((item: string) => item.trim()).filter((item: string) => item);
+
+// Sorted list of a list field, from a table array or a comma-separated API value
+const normalizeList = (value: string[] | string | undefined): string[] =>
+  (Array.isArray(value) ? value.flatMap(splitList) : splitList(value || '')).sort();
+
 // Column definition for the table
 interface TableColumn {
   key: keyof NetworkConfigRow;
@@ -743,12 +751,12 @@ const ProvisioningTable: React.FC<ProvisioningTableProps> = ({
           server_ifname: apiData.serverInterface || '',
           link_speed: apiSpeed, // Populate from merged API data
           link_group_lag_mode: apiLagMode,
-          link_group_ct_names: apiCtNames,
+          link_group_ct_names: splitList(apiCtNames),
           link_group_ifname: apiLagIfname, // Populate from merged API data
           is_external: false,
-          server_tags: '',
+          server_tags: [],
           switch_tags: '',
-          link_tags: '',
+          link_tags: [],
           comment: 'Only in Blueprint' // Mark as blueprint-only connection
         };
         
@@ -949,9 +957,9 @@ const ProvisioningTable: React.FC<ProvisioningTableProps> = ({
     const apiLagIfname = apiData.ae1?.if_name || apiData.rawData?.ae1?.if_name || apiData.ae_interface?.name || apiData.rawData?.ae_interface?.name || '';
     const apiExternal = apiData.is_external || apiData.rawData?.is_external || false;
 
-    // CT comparison with proper comma-separated list handling
-    const excelCTs = (row.link_group_ct_names || '').split(',').map((ct: string) => ct.trim()).filter((ct: string) => ct).sort();
-    const apiCTsList = (apiCtNames || '').split(',').map((ct: string) => ct.trim()).filter((ct: string) => ct).sort();
+    // CT comparison ignores list order
+    const excelCTs = normalizeList(row.link_group_ct_names);
+    const apiCTsList = normalizeList(apiCtNames);
     const ctNamesMatch = JSON.stringify(excelCTs) === JSON.stringify(apiCTsList);
 
     // Debug the specific problem case during field comparison
@@ -1016,6 +1024,7 @@ const ProvisioningTable: React.FC<ProvisioningTableProps> = ({
 
   const formatCellValue = (value: any, columnKey: string): string => {
     if (value === null || value === undefined) return '';
+    if (Array.isArray(value)) return value.join(', ');
     
     switch (columnKey) {
       case 'is_external':
@@ -1070,7 +1079,7 @@ const ProvisioningTable: React.FC<ProvisioningTableProps> = ({
         apiValue = apiData.evpn1?.lag_mode || apiData.lag_mode || '';
         break;
       case 'link_group_ct_names':
-        apiValue = apiData.ct_names || apiData.CT?.label || '';
+        apiValue = normalizeList(apiData.ct_names || apiData.CT?.label);
         break;
       case 'link_group_ifname':
         apiValue = apiData.ae1?.if_name || apiData.ae_interface?.name || '';
@@ -1083,24 +1092,27 @@ const ProvisioningTable: React.FC<ProvisioningTableProps> = ({
         apiValue = '';
     }
 
+    // List fields are compared in the same order on both sides
+    const excelFormatted = Array.isArray(value) ? formatCellValue(normalizeList(value), columnKey) : formattedValue;
     const apiFormatted = formatCellValue(apiValue, columnKey);
-    const matches = formattedValue === apiFormatted;
+    const matches = excelFormatted === apiFormatted;
 
     if (matches) {
-      return `Excel: ${formattedValue} ✓ (matches Apstra)`;
+      return `Excel: ${excelFormatted} ✓ (matches Apstra)`;
     } else {
-      return `Excel: ${formattedValue}\nApstra: ${apiFormatted || 'N/A'}\n⚠️ Values differ`;
+      return `Excel: ${excelFormatted}\nApstra: ${apiFormatted || 'N/A'}\n⚠️ Values differ`;
     }
   };
 
   const getCellClass = (value: any, columnKey: string, row?: NetworkConfigRow): string => {
     let baseClass = 'table-cell';
+    const hasValue = Array.isArray(value) ? value.length > 0 : Boolean(value);
     
     if (columnKey === 'is_external') {
       baseClass += value === true ? ' external-yes' : value === false ? ' external-no' : '';
     }
     
-    if (!value) {
+    if (!hasValue) {
       baseClass += ' empty-cell';
     }
 
@@ -1140,7 +1152,7 @@ const ProvisioningTable: React.FC<ProvisioningTableProps> = ({
       } else {
         // No API data fetched yet - show initial XLSX state
         const comparableFields = ['switch_label', 'server_label', 'switch_ifname', 'server_ifname', 'link_speed', 'link_group_lag_mode', 'link_group_ct_names', 'is_external'];
-        if (comparableFields.includes(columnKey) && value) {
+        if (comparableFields.includes(columnKey) && hasValue) {
           baseClass += ' field-xlsx-pending'; // Light gray for initial XLSX state
         }
       }
@@ -34,12 +34,12 @@ describe('ProvisioningTable', () => {
       server_ifname: 'ens1',
       link_speed: '10G',
       link_group_lag_mode: '',
-      link_group_ct_names: '',
+      link_group_ct_names: [],
       link_group_ifname: '',
       is_external: false,
-      server_tags: '',
+      server_tags: [],
       switch_tags: '',
-      link_tags: '',
+      link_tags: [],
       comment: ''
     },
     {
@@ -50,12 +50,12 @@ describe('ProvisioningTable', () => {
       server_ifname: 'ens2',
       link_speed: '25G',
       link_group_lag_mode: '',
-      link_group_ct_names: '',
+      link_group_ct_names: [],
       link_group_ifname: '',
       is_external: false,
-      server_tags: '',
+      server_tags: [],
       switch_tags: '',
-      link_tags: '',
+      link_tags: [],
       comment: ''
     },
     {
@@ -66,12 +66,12 @@ describe('ProvisioningTable', () => {
       server_ifname: 'ens3',
       link_speed: '10G',
       link_group_lag_mode: '',
-      link_group_ct_names: '',
+      link_group_ct_names: [],
       link_group_ifname: '',
       is_external: false,
-      server_tags: '',
+      server_tags: [],
       switch_tags: '',
-      link_tags: '',
+      link_tags: [],
       comment: ''
     },
     {
@@ -82,12 +82,12 @@ describe('ProvisioningTable', () => {
       server_ifname: 'ens1',
       link_speed: '25G',
       link_group_lag_mode: '',
-      link_group_ct_names: '',
+      link_group_ct_names: [],
       link_group_ifname: '',
       is_external: false,
-      server_tags: '',
+      server_tags: [],
       switch_tags: '',
-      link_tags: '',
+      link_tags: [],
       comment: ''
     },
   ];
@@ -129,12 +129,12 @@ describe('ProvisioningTable', () => {
           server_ifname: 'ens1',
           link_speed: '10G',
           link_group_lag_mode: '',
-          link_group_ct_names: '',
+          link_group_ct_names: [],
           link_group_ifname: '',
           is_external: false,
-          server_tags: '',
+          server_tags: [],
           switch_tags: '',
-          link_tags: '',
+          link_tags: [],
           comment: ''
         },
         {
@@ -145,12 +145,12 @@ describe('ProvisioningTable', () => {
           server_ifname: 'ens2',
           link_speed: '25G',
           link_group_lag_mode: '',
-          link_group_ct_names: '',
+          link_group_ct_names: [],
           link_group_ifname: '',
           is_external: false,
-          server_tags: '',
+          server_tags: [],
           switch_tags: '',
-          link_tags: '',
+          link_tags: [],
           comment: ''
         },
         {
@@ -161,12 +161,12 @@ describe('ProvisioningTable', () => {
           server_ifname: 'ens3',
           link_speed: '1G',
           link_group_lag_mode: '',
-          link_group_ct_names: '',
+          link_group_ct_names: [],
           link_group_ifname: '',
           is_external: false,
-          server_tags: '',
+          server_tags: [],
           switch_tags: '',
-          link_tags: '',
+          link_tags: [],
           comment: ''
         },
       ];
@@ -199,12 +199,12 @@ describe('ProvisioningTable', () => {
           server_ifname: 'ens1',
           link_speed: '10G',
           link_group_lag_mode: '',
-          link_group_ct_names: '',
+          link_group_ct_names: [],
           link_group_ifname: '',
           is_external: false,
-          server_tags: '',
+          server_tags: [],
           switch_tags: '',
-          link_tags: '',
+          link_tags: [],
           comment: ''
         },
         {
@@ -215,12 +215,12 @@ describe('ProvisioningTable', () => {
           server_ifname: 'ens2',
           link_speed: '25G',
           link_group_lag_mode: '',
-          link_group_ct_names: '',
+          link_group_ct_names: [],
           link_group_ifname: '',
           is_external: false,
-          server_tags: '',
+          server_tags: [],
           switch_tags: '',
-          link_tags: '',
+          link_tags: [],
           comment: ''
         },
       ];
@@ -275,12 +275,12 @@ describe('ProvisioningTable', () => {
           server_ifname: 'ens1',
           link_speed: '10G',
           link_group_lag_mode: '',
-          link_group_ct_names: '',
+          link_group_ct_names: [],
           link_group_ifname: '',
           is_external: false,
-          server_tags: '',
+          server_tags: [],
           switch_tags: '',
-          link_tags: '',
+          link_tags: [],
           comment: 'Only in Blueprint'
         },
         {
@@ -291,12 +291,12 @@ describe('ProvisioningTable', () => {
           server_ifname: 'ens2',
           link_speed: '25G',
           link_group_lag_mode: '',
-          link_group_ct_names: '',
+          link_group_ct_names: [],
           link_group_ifname: '',
           is_external: false,
-          server_tags: '',
+          server_tags: [],
           switch_tags: '',
-          link_tags: '',
+          link_tags: [],
           comment: ''
         },
       ];
@@ -394,6 +394,26 @@ describe('ProvisioningTable', () => {
     });
   });
 
+  describe('List Fields', () => {
+    it('should only mark non-empty list cells as pending XLSX values', () => {
+      const testData = createMockData();
+      testData[0].link_group_ct_names = ['CT-B', 'CT-A'];
+
+      const { container } = render(
+        <ProvisioningTable
+          data={testData}
+          isLoading={false}
+          onProvision={mockOnProvision}
+          onDataUpdate={mockOnDataUpdate}
+        />
+      );
+
+      const pendingCells = Array.from(container.querySelectorAll('.field-xlsx-pending'));
+      expect(pendingCells.some(cell => cell.textContent === 'CT-B, CT-A')).toBe(true);
+      expect(pendingCells.every(cell => cell.textContent !== '')).toBe(true);
+    });
+  });
+
   describe('Error Handling', () => {
     it('should handle empty data gracefully', () => {
       render(
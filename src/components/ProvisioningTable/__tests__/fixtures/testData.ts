@@ -14,12 +14,12 @@ export const multiServerTestData: NetworkConfigRow[] = [
     server_ifname: 'ens192',
     link_speed: '10G',
     link_group_lag_mode: '',
-    link_group_ct_names: 'Web_CT',
+    link_group_ct_names: ['Web_CT'],
     link_group_ifname: '',
     is_external: false,
-    server_tags: 'web,frontend',
+    server_tags: ['web', 'frontend'],
     switch_tags: 'access',
-    link_tags: 'prod',
+    link_tags: ['prod'],
     comment: ''
   },
   {
@@ -30,12 +30,12 @@ export const multiServerTestData: NetworkConfigRow[] = [
     server_ifname: 'ens192',
     link_speed: '25G',
     link_group_lag_mode: 'active',
-    link_group_ct_names: 'DB_CT',
+    link_group_ct_names: ['DB_CT'],
     link_group_ifname: 'ae0',
     is_external: false,
-    server_tags: 'database,backend',
+    server_tags: ['database', 'backend'],
     switch_tags: 'core',
-    link_tags: 'prod',
+    link_tags: ['prod'],
     comment: ''
   },
   {
@@ -46,12 +46,12 @@ export const multiServerTestData: NetworkConfigRow[] = [
     server_ifname: 'ens224',
     link_speed: '10G',
     link_group_lag_mode: '',
-    link_group_ct_names: 'Web_CT',
+    link_group_ct_names: ['Web_CT'],
     link_group_ifname: '',
     is_external: false,
-    server_tags: 'web,frontend',
+    server_tags: ['web', 'frontend'],
     switch_tags: 'access',
-    link_tags: 'prod',
+    link_tags: ['prod'],
     comment: ''
   },
   {
@@ -62,12 +62,12 @@ export const multiServerTestData: NetworkConfigRow[] = [
     server_ifname: 'ens192',
     link_speed: '25G',
     link_group_lag_mode: '',
-    link_group_ct_names: 'App_CT',
+    link_group_ct_names: ['App_CT'],
     link_group_ifname: '',
     is_external: true,
-    server_tags: 'application,middleware',
+    server_tags: ['application', 'middleware'],
     switch_tags: 'dmz',
-    link_tags: 'prod',
+    link_tags: ['prod'],
     comment: 'DMZ connection'
   },
   {
@@ -78,12 +78,12 @@ export const multiServerTestData: NetworkConfigRow[] = [
     server_ifname: 'ens224',
     link_speed: '25G',
     link_group_lag_mode: 'active',
-    link_group_ct_names: 'DB_CT',
+    link_group_ct_names: ['DB_CT'],
     link_group_ifname: 'ae0',
     is_external: false,
-    server_tags: 'database,backend',
+    server_tags: ['database', 'backend'],
     switch_tags: 'core',
-    link_tags: 'prod',
+    link_tags: ['prod'],
     comment: ''
   }
 ];
@@ -97,12 +97,12 @@ export const singleServerTestData: NetworkConfigRow[] = [
     server_ifname: 'ens192',
     link_speed: '10G',
     link_group_lag_mode: '',
-    link_group_ct_names: '',
+    link_group_ct_names: [],
     link_group_ifname: '',
     is_external: false,
-    server_tags: '',
+    server_tags: [],
     switch_tags: '',
-    link_tags: '',
+    link_tags: [],
     comment: ''
   }
 ];
@@ -116,12 +116,12 @@ export const blueprintOnlyTestData: NetworkConfigRow[] = [
     server_ifname: 'ens192',
     link_speed: '10G',
     link_group_lag_mode: '',
-    link_group_ct_names: '',
+    link_group_ct_names: [],
     link_group_ifname: '',
     is_external: false,
-    server_tags: '',
+    server_tags: [],
     switch_tags: '',
-    link_tags: '',
+    link_tags: [],
     comment: ''
   },
   {
@@ -132,12 +132,12 @@ export const blueprintOnlyTestData: NetworkConfigRow[] = [
     server_ifname: 'ens224',
     link_speed: '25G',
     link_group_lag_mode: '',
-    link_group_ct_names: '',
+    link_group_ct_names: [],
     link_group_ifname: '',
     is_external: false,
-    server_tags: '',
+    server_tags: [],
     switch_tags: '',
-    link_tags: '',
+    link_tags: [],
     comment: 'Only in Blueprint'
   },
   {
@@ -148,12 +148,12 @@ export const blueprintOnlyTestData: NetworkConfigRow[] = [
     server_ifname: 'ens256',
     link_speed: '40G',
     link_group_lag_mode: '',
-    link_group_ct_names: '',
+    link_group_ct_names: [],
     link_group_ifname: '',
     is_external: false,
-    server_tags: '',
+    server_tags: [],
     switch_tags: '',
-    link_tags: '',
+    link_tags: [],
     comment: 'Only in Blueprint'
   }
 ];
@@ -168,12 +168,12 @@ export const edgeCaseTestData: NetworkConfigRow[] = [
     server_ifname: 'ens192',
     link_speed: '10G',
     link_group_lag_mode: '',
-    link_group_ct_names: '',
+    link_group_ct_names: [],
     link_group_ifname: '',
     is_external: false,
-    server_tags: '',
+    server_tags: [],
     switch_tags: '',
-    link_tags: '',
+    link_tags: [],
     comment: ''
   },
   // Null-like values
@@ -185,12 +185,12 @@ export const edgeCaseTestData: NetworkConfigRow[] = [
     server_ifname: '',
     link_speed: '',
     link_group_lag_mode: '',
-    link_group_ct_names: '',
+    link_group_ct_names: [],
     link_group_ifname: '',
     is_external: false,
-    server_tags: '',
+    server_tags: [],
     switch_tags: '',
-    link_tags: '',
+    link_tags: [],
     comment: ''
   },
   // Special characters in names
@@ -202,12 +202,12 @@ export const edgeCaseTestData: NetworkConfigRow[] = [
     server_ifname: 'bond0.100',
     link_speed: '100G',
     link_group_lag_mode: 'active-backup',
-    link_group_ct_names: 'Special_CT-Name',
+    link_group_ct_names: ['Special_CT-Name'],
     link_group_ifname: 'ae-0',
     is_external: true,
-    server_tags: 'tag1,tag2,tag3',
+    server_tags: ['tag1', 'tag2', 'tag3'],
     switch_tags: 'switch-tag',
-    link_tags: 'link-tag',
+    link_tags: ['link-tag'],
     comment: 'Special characters test'
   }
 ];
@@ -284,12 +284,12 @@ export const generateLargeTestDataset = (serverCount: number, connectionsPerServ
         server_ifname: `ens${192 + c}`,
         link_speed: c % 2 === 0 ? '25G' : '10G',
         link_group_lag_mode: c > 2 ? 'active' : '',
-        link_group_ct_names: `CT_${s}`,
+        link_group_ct_names: [`CT_${s}`],
         link_group_ifname: c > 2 ? `ae${c - 2}` : '',
         is_external: s % 10 === 0, // Every 10th server is external
-        server_tags: `server-${s}`,
+        server_tags: [`server-${s}`],
         switch_tags: `switch-tag`,
-        link_tags: `link-${c}`,
+        link_tags: [`link-${c}`],
         comment: ''
       });
     }
@@ -3,7 +3,7 @@ import './SheetSelector.module.css';
 
 interface SheetSelectorProps {
   sheets?: string[];
-  filePath?: string;
+  fileId?: string;
   onSheetSelect?: (sheetName: string) => void;
   selectedSheet?: string;
 }
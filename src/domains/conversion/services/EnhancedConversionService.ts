@@ -1,6 +1,7 @@
 import { invoke } from '@tauri-apps/api/tauri';
 
 export type MappingType = 'Exact' | 'Partial' | 'Regex' | 'Fuzzy';
+export type SimilarityMetric = 'levenshtein' | 'jaro_winkler' | 'token_set';
 export type DataType = 'String' | 'Number' | 'Boolean' | 'Date' | 'Array' | 'Json';
 
 export interface ValidationError {
@@ -14,6 +15,8 @@ export interface XlsxMapping {
   mapping_type: MappingType;
   priority: number;
   case_sensitive: boolean;
+  similarity?: SimilarityMetric;
+  min_confidence?: number;
 }
 
 export interface ApiMapping {
@@ -41,6 +44,22 @@ export interface UiConfig {
   hidden: boolean;
 }
 
+export type MergeBehavior = 'when_merged' | 'always' | 'never';
+
+export interface CellCoercion {
+  numbers?: 'auto' | 'integer';
+  dates?: 'serial' | 'iso' | 'month_day' | 'day_month';
+  reject_errors?: boolean; // Defaults to true
+}
+
+export interface ArrayFormat {
+  delimiters?: string[]; // Defaults to [",", "\n"]
+  trim?: boolean; // Defaults to true
+  dedupe?: boolean; // Defaults to true
+  sort?: boolean; // Defaults to false
+  join?: string; // Defaults to ", "
+}
+
 export interface FieldDefinition {
   display_name: string;
   description: string;
@@ -51,6 +70,9 @@ export interface FieldDefinition {
   api_mappings: ApiMapping[];
   validation_rules: ValidationRules;
   ui_config: UiConfig;
+  merge_behavior?: MergeBehavior;
+  cell_coercion?: CellCoercion;
+  array_format?: ArrayFormat; // Used by Array fields
 }
 
 export interface TransformationLogic {
@@ -61,8 +83,24 @@ export interface TransformationLogic {
     step_type: string;
     parameters: Record<string, any>;
   }>;
+  Conditional?: {
+    branches: Array<{ if: Condition; then: TransformationLogic }>;
+    else?: TransformationLogic;
+  };
 }
 
+// Without `field` a condition tests the value being transformed
+export type Condition =
+  | { type: 'equals'; field?: string; value: string; case_sensitive?: boolean }
+  | { type: 'matches'; field?: string; pattern: string }
+  | { type: 'in_set'; field?: string; values: string[]; case_sensitive?: boolean }
+  | { type: 'empty'; field?: string }
+  | { type: 'truthy'; field?: string }
+  | { type: 'compare'; field?: string; op: 'eq' | 'ne' | 'lt' | 'le' | 'gt' | 'ge'; value: number }
+  | { type: 'and'; conditions: Condition[] }
+  | { type: 'or'; conditions: Condition[] }
+  | { type: 'not'; condition: Condition };
+
 export interface TransformationRule {
   name: string;
   description: string;
@@ -72,11 +110,30 @@ export interface TransformationRule {
   priority: number;
 }
 
+export interface HeaderDetectionConfig {
+  mode: 'fixed' | 'auto';
+  max_rows: number;
+}
+
+export interface PortRangeExpansion {
+  fields?: string[]; // Defaults to switch_ifname and server_ifname
+}
+
+export type BlueprintSource =
+  | { type: 'column' }
+  | { type: 'cell'; cell: string }
+  | { type: 'sheet_name'; pattern: string }
+  | { type: 'default'; value: string };
+
 export interface EnhancedConversionMap {
   version: string;
   header_row?: number;
+  header_row_span?: number;
+  header_detection?: HeaderDetectionConfig;
   field_definitions: Record<string, FieldDefinition>;
   transformation_rules: Record<string, TransformationRule>;
+  blueprint_sources?: BlueprintSource[];
+  port_range_expansion?: PortRangeExpansion;
   created_at?: string;
   updated_at?: string;
 }
@@ -90,6 +147,41 @@ export interface HeaderConversionResult {
     severity: 'Error' | 'Warning' | 'Info';
   }>;
   mapping_confidence: Record<string, number>;
+  conflicts: HeaderMappingConflict[];
+  ambiguous_headers: AmbiguousHeader[];
+}
+
+// A header that matched a field already assigned to a better ranked header
+export interface HeaderMappingConflict {
+  field_name: string;
+  assigned_header: string;
+  rejected_header: string;
+  tie: boolean; // Same confidence and priority; the leftmost header won
+}
+
+// A header that matched several fields with the same confidence and priority
+export interface AmbiguousHeader {
+  header: string;
+  assigned_field: string;
+  tied_fields: string[];
+}
+
+export interface HeaderMatchCandidate {
+  field_name: string;
+  confidence: number;
+  matched_pattern?: string; // The xlsx mapping that matched; absent for a name similarity suggestion
+}
+
+export interface HeaderMappingSuggestion {
+  header: string;
+  mapped_field?: string; // The field header conversion picks
+  candidates: HeaderMatchCandidate[]; // Up to 3, best first
+}
+
+export interface HeaderMappingPreview {
+  headers: HeaderMappingSuggestion[];
+  unmatched_fields: string[];
+  missing_required_fields: string[];
 }
 
 export interface ValidationResult {
@@ -168,6 +260,18 @@ class EnhancedConversionService {
     }
   }
 
+  static async previewHeaderMapping(
+    excelHeaders: string[], 
+    enhancedMap: EnhancedConversionMap
+  ): Promise<HeaderMappingPreview> {
+    try {
+      return await invoke('preview_header_mapping', { excelHeaders, enhancedMap });
+    } catch (error) {
+      console.error('Failed to preview header mapping:', error);
+      throw new Error(`Failed to preview header mapping: ${error}`);
+    }
+  }
+
   static async applyFieldTransformations(
     fieldData: Record<string, string>, 
     enhancedMap: EnhancedConversionMap
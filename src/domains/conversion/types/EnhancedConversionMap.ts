@@ -1,6 +1,7 @@
 // Re-export enhanced conversion types from the service
 export type {
   MappingType,
+  SimilarityMetric,
   DataType,
   ValidationError,
   XlsxMapping,
@@ -8,10 +9,22 @@ export type {
   ValidationRules,
   UiConfig,
   FieldDefinition,
+  MergeBehavior,
+  CellCoercion,
+  ArrayFormat,
   TransformationLogic,
+  Condition,
   TransformationRule,
   EnhancedConversionMap,
+  HeaderDetectionConfig,
+  BlueprintSource,
+  PortRangeExpansion,
   HeaderConversionResult,
+  HeaderMappingPreview,
+  HeaderMappingSuggestion,
+  HeaderMatchCandidate,
+  HeaderMappingConflict,
+  AmbiguousHeader,
   ValidationResult,
   ApiExtractionResult,
   TableColumnDefinition
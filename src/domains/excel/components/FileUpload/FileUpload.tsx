@@ -1,10 +1,12 @@
 import React, { useState } from 'react';
 import { open } from '@tauri-apps/api/dialog';
 import { invoke } from '@tauri-apps/api/tauri';
+import { UploadedFile } from '../../../shared/types';
 import './FileUpload.module.css';
 
 interface FileUploadProps {
-  onSheetsLoaded?: (sheets: string[], filePath: string) => void;
+  // Later commands take the upload's file id in place of a path
+  onSheetsLoaded?: (sheets: string[], fileId: string, fileName: string) => void;
 }
 
 const FileUpload: React.FC<FileUploadProps> = ({ onSheetsLoaded }) => {
@@ -20,8 +22,8 @@ const FileUpload: React.FC<FileUploadProps> = ({ onSheetsLoaded }) => {
       const selected = await open({
         multiple: false,
         filters: [{
-          name: 'Excel Files',
-          extensions: ['xlsx']
+          name: 'Spreadsheet Files',
+          extensions: ['xlsx', 'xlsm', 'xls', 'xlsb', 'ods']
         }]
       });
 
@@ -32,11 +34,11 @@ const FileUpload: React.FC<FileUploadProps> = ({ onSheetsLoaded }) => {
 
       const filePath = selected as string;
 
-      // Process the Excel file and get sheet names  
-      const sheets = await invoke<string[]>('upload_excel_file', { filePath });
+      // Copy the Excel file into the app's temp directory and get sheet names
+      const upload = await invoke<UploadedFile>('upload_excel_file', { filePath });
       
       if (onSheetsLoaded) {
-        onSheetsLoaded(sheets, filePath);
+        onSheetsLoaded(upload.sheet_names, upload.file_id, upload.file_name);
       }
 
     } catch (error) {
@@ -4,6 +4,7 @@ import { ExcelProcessingService } from '../services';
 export const useFileUpload = () => {
   const [isUploading, setIsUploading] = useState(false);
   const [sheets, setSheets] = useState<string[]>([]);
+  const [fileId, setFileId] = useState<string | null>(null);
   const [error, setError] = useState<string | null>(null);
 
   const uploadFile = async (filePath: string) => {
@@ -11,8 +12,9 @@ export const useFileUpload = () => {
     setError(null);
     
     try {
-      const sheetNames = await ExcelProcessingService.uploadFile(filePath);
-      setSheets(sheetNames);
+      const upload = await ExcelProcessingService.uploadFile(filePath);
+      setSheets(upload.sheet_names);
+      setFileId(upload.file_id);
     } catch (err) {
       setError(err as string);
     } finally {
@@ -23,6 +25,7 @@ export const useFileUpload = () => {
   return {
     isUploading,
     sheets,
+    fileId,
     error,
     uploadFile,
   };
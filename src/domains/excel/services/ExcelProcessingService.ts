@@ -1,13 +1,51 @@
 import { invoke } from '@tauri-apps/api/tauri';
-import { NetworkConfigRow } from '../../shared/types';
+import { ExcelTableInfo, NetworkConfigRow, ParsedSheetData, ParsedWorkbookData, SheetParseOptions, SheetPreview, UploadedFile, WorkbookDiff } from '../../shared/types';
 
 export class ExcelProcessingService {
-  static async uploadFile(filePath: string): Promise<string[]> {
+  static async uploadFile(filePath: string): Promise<UploadedFile> {
     return await invoke('upload_excel_file', { filePath });
   }
 
-  static async parseSheet(filePath: string, sheetName: string): Promise<NetworkConfigRow[]> {
-    return await invoke('parse_excel_sheet', { filePath, sheetName });
+  static async parseSheet(fileId: string, sheetName: string, options?: SheetParseOptions): Promise<NetworkConfigRow[]> {
+    return await invoke('parse_excel_sheet', { fileId, sheetName, options });
+  }
+
+  static async parseSheetWithReport(fileId: string, sheetName: string, options?: SheetParseOptions): Promise<ParsedSheetData> {
+    return await invoke('parse_excel_sheet_with_report', { fileId, sheetName, options });
+  }
+
+  static async parseSheets(fileId: string, sheetNames?: string[], options?: SheetParseOptions): Promise<ParsedWorkbookData> {
+    return await invoke('parse_excel_sheets', { fileId, sheetNames, options });
+  }
+
+  static async diffWorkbooks(oldFileId: string, newFileId: string, sheetNames?: string[], options?: SheetParseOptions): Promise<WorkbookDiff> {
+    return await invoke('diff_excel_workbooks', { oldFileId, newFileId, sheetNames, options });
+  }
+
+  static async listTables(fileId: string): Promise<ExcelTableInfo[]> {
+    return await invoke('list_excel_tables', { fileId });
+  }
+
+  static async parseTable(fileId: string, tableName: string, options?: SheetParseOptions): Promise<ParsedSheetData> {
+    return await invoke('parse_excel_table', { fileId, tableName, options });
+  }
+
+  static async previewSheet(fileId: string, sheetName: string, maxRows?: number): Promise<SheetPreview> {
+    return await invoke('preview_excel_sheet', { fileId, sheetName, maxRows });
+  }
+
+  static async parseCsvFile(
+    fileId: string,
+    options: { headerRow?: number; delimiter?: string; encoding?: string } = {}
+  ): Promise<NetworkConfigRow[]> {
+    return await invoke('parse_csv_file', { fileId, ...options });
+  }
+
+  static async parseCsvFileWithReport(
+    fileId: string,
+    options: { headerRow?: number; delimiter?: string; encoding?: string; options?: SheetParseOptions } = {}
+  ): Promise<ParsedSheetData> {
+    return await invoke('parse_csv_file_with_report', { fileId, ...options });
   }
 
   static async validateData(data: NetworkConfigRow[]): Promise<NetworkConfigRow[]> {
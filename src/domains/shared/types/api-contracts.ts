@@ -9,18 +9,25 @@ export interface NetworkConfigRow {
   blueprint?: string;
   server_label?: string;
   is_external?: boolean;
-  server_tags?: string;
+  server_tags?: string[];
   switch_tags?: string;
   link_group_ifname?: string;
   link_group_lag_mode?: string;
-  link_group_ct_names?: string;
+  link_group_ct_names?: string[];
   link_group_tags?: string;
   link_speed?: string;
   server_ifname?: string;
   switch_label?: string;
   switch_ifname?: string;
-  link_tags?: string;
+  link_tags?: string[];
   comment?: string;
+  source?: RowSource;
+}
+
+export interface RowSource {
+  sheet_name: string;
+  row: number; // 1-based sheet row
+  columns: Record<string, string>; // Field name -> source column letter (e.g. "F")
 }
 
 // ============================================================================
@@ -144,14 +151,134 @@ export interface ExcelSheetData {
   total_rows: number;
 }
 
+export interface SheetParseOptions {
+  range?: string; // A1-style range such as "A5:N400" limiting data rows and columns
+  include?: string[]; // Row predicates that must all match, e.g. 'switch_label ~ "^leaf-a"'
+  exclude?: string[]; // Rows matching any of these are dropped, e.g. 'comment == "DECOM"'
+}
+
+export interface UploadedFile {
+  file_id: string; // Opaque id accepted by later commands in place of a file path
+  file_name: string;
+  size: number;
+  sha256: string;
+  sheet_names: string[];
+}
+
+export interface ExcelTableInfo {
+  name: string;
+  sheet_name: string;
+  columns: string[]; // Table column names, used as headers
+  range: string; // A1-style range of the table body, e.g. "B4:G40"
+  row_count: number;
+}
+
+export interface ParsedSheetData {
+  rows: NetworkConfigRow[];
+  report: ParseReport;
+}
+
+export interface ParsedWorkbookData {
+  rows: NetworkConfigRow[]; // Rows of all sheets, in sheet order
+  sheet_reports: SheetParseReport[];
+  duplicates: DuplicateRowGroup[]; // switch_label + switch_ifname found on more than one sheet
+}
+
+// Row-level changes between two versions of a workbook, keyed by switch_label + switch_ifname
+export interface WorkbookDiff {
+  added: NetworkConfigRow[]; // Rows of the new version only
+  removed: NetworkConfigRow[]; // Rows of the old version only
+  modified: ModifiedRow[];
+  unchanged_count: number;
+}
+
+export interface ModifiedRow {
+  switch_label: string;
+  switch_ifname: string;
+  changes: FieldChange[];
+  previous_source?: RowSource; // Where the row was in the old version
+  row: NetworkConfigRow; // The row as it is in the new version
+}
+
+export interface FieldChange {
+  field: string;
+  old_value?: string;
+  new_value?: string;
+}
+
+export interface SheetParseReport {
+  sheet_name: string;
+  report: ParseReport;
+}
+
+export interface DuplicateRowGroup {
+  switch_label: string;
+  switch_ifname: string;
+  occurrences: RowSource[];
+}
+
+export interface ParseReport {
+  header_row?: number; // 1-based sheet row of the (first) header row
+  unmapped_headers: string[];
+  total_rows: number; // Non-empty data rows
+  expanded_rows: number; // Rows after port range expansion, the unit of parsed/filtered/skipped
+  parsed_rows: number;
+  skipped_rows: number;
+  degraded_rows: number;
+  filtered_rows: number; // Rows dropped by include/exclude predicates
+  row_issues: RowParseIssue[];
+}
+
+export interface RowParseIssue extends ProcessingError {
+  status: RowParseStatus; // row_index is the 1-based sheet row
+  missing_required_fields: string[];
+}
+
+export enum RowParseStatus {
+  Skipped = 'Skipped',
+  Degraded = 'Degraded',
+}
+
+export interface SheetPreview {
+  sheet_name: string;
+  first_row: number; // 1-based sheet row of the first preview row
+  first_column: string; // Column letter of the first cell in each row
+  total_rows: number; // Rows in the sheet's used range, not just the preview
+  total_columns: number;
+  has_merge_metadata: boolean;
+  rows: PreviewCell[][];
+}
+
+export interface PreviewCell {
+  reference: string; // A1-style reference, e.g. "B3"
+  cell_type: PreviewCellType;
+  value: string | number | boolean | null;
+  merge?: MergeSpan;
+}
+
+export type PreviewCellType = 'Empty' | 'String' | 'Int' | 'Float' | 'Bool' | 'DateTime' | 'Duration' | 'Error';
+
+export interface MergeSpan {
+  anchor: string; // Reference of the merged region's top-left cell
+  row_span: number;
+  col_span: number;
+  is_anchor: boolean;
+}
+
 // ============================================================================
 // Tauri Command Contracts
 // ============================================================================
 
 export interface TauriCommands {
   // Excel Domain Commands
-  'upload_excel_file': (filePath: string) => Promise<string[]>;
-  'parse_excel_sheet': (filePath: string, sheetName: string) => Promise<NetworkConfigRow[]>;
+  'upload_excel_file': (filePath: string) => Promise<UploadedFile>;
+  'parse_excel_sheet': (fileId: string, sheetName: string, options?: SheetParseOptions) => Promise<NetworkConfigRow[]>;
+  'parse_excel_sheet_with_report': (fileId: string, sheetName: string, options?: SheetParseOptions) => Promise<ParsedSheetData>;
+  'parse_excel_sheets': (fileId: string, sheetNames?: string[], options?: SheetParseOptions) => Promise<ParsedWorkbookData>;
+  'diff_excel_workbooks': (oldFileId: string, newFileId: string, sheetNames?: string[], options?: SheetParseOptions) => Promise<WorkbookDiff>;
+  'list_excel_tables': (fileId: string) => Promise<ExcelTableInfo[]>;
+  'parse_excel_table': (fileId: string, tableName: string, options?: SheetParseOptions) => Promise<ParsedSheetData>;
+  'preview_excel_sheet': (fileId: string, sheetName: string, maxRows?: number) => Promise<SheetPreview>;
   
   // Apstra Domain Commands
   'apstra_login': (config: ApstraConfig) => Promise<AuthResult>;
@@ -161,6 +288,7 @@ export interface TauriCommands {
   
   // Conversion Domain Commands
   'convert_headers_enhanced': (headers: string[], map: ConversionMap) => Promise<ApiResponse<any>>;
+  'preview_header_mapping': (headers: string[], map: ConversionMap) => Promise<any>;
   'load_enhanced_conversion_map': () => Promise<ApiResponse<ConversionMap>>;
   'save_enhanced_conversion_map': (map: ConversionMap) => Promise<ApiResponse<boolean>>;
   
@@ -170,7 +298,7 @@ export interface TauriCommands {
   
   // Shared Commands
   'send_backend_log': (level: string, message: string) => Promise<void>;
-  'cleanup_temp_file': (filePath: string) => Promise<boolean>;
+  'cleanup_temp_file': (fileId: string) => Promise<void>;
 }
 
 // ============================================================================
@@ -57,12 +57,12 @@ export class ProvisioningEntryService {
         },
         
         connectivity: {
-          templates_input: row.link_group_ct_names,
+          templates_input: row.link_group_ct_names?.join(','),
         },
         
         tags: {
-          server_input: row.server_tags,
-          link_input: row.link_tags,
+          server_input: row.server_tags?.join(','),
+          link_input: row.link_tags?.join(','),
           switch_input: row.switch_tags,
         },
         
@@ -356,9 +356,9 @@ export class ProvisioningEntryService {
         is_external: entry.network.external_input ?? entry.network.external_fetched,
         link_group_ifname: entry.lag.name_input || entry.lag.name_fetched,
         link_group_lag_mode: entry.lag.mode_input || entry.lag.mode_fetched,
-        link_group_ct_names: entry.connectivity.templates_input || entry.connectivity.templates_fetched,
-        server_tags: entry.tags.server_input || entry.tags.server_fetched,
-        link_tags: entry.tags.link_input || entry.tags.link_fetched,
+        link_group_ct_names: this.splitList(entry.connectivity.templates_input || entry.connectivity.templates_fetched),
+        server_tags: this.splitList(entry.tags.server_input || entry.tags.server_fetched),
+        link_tags: this.splitList(entry.tags.link_input || entry.tags.link_fetched),
         switch_tags: entry.tags.switch_input || entry.tags.switch_fetched,
         comment: entry.metadata.comment,
       };
@@ -369,6 +369,13 @@ export class ProvisioningEntryService {
     return rows;
   }
   
+  /**
+   * Split a comma-separated entry value back into a list
+   */
+  private static splitList(value?: string): string[] {
+    return (value || '').split(',').map(item => item.trim()).filter(item => item);
+  }
+  
   /**
    * Speed normalization helper
    */
@@ -1,13 +1,14 @@
 import { invoke } from '@tauri-apps/api/tauri';
 import { NetworkConfigRow, ProcessingResult } from '../types';
+import { UploadedFile } from '../domains/shared/types';
 
 export class ApiService {
-  static async uploadExcelFile(filePath: string): Promise<string[]> {
+  static async uploadExcelFile(filePath: string): Promise<UploadedFile> {
     return await invoke('upload_excel_file', { filePath });
   }
 
-  static async parseExcelSheet(filePath: string, sheetName: string): Promise<NetworkConfigRow[]> {
-    return await invoke('parse_excel_sheet', { filePath, sheetName });
+  static async parseExcelSheet(fileId: string, sheetName: string): Promise<NetworkConfigRow[]> {
+    return await invoke('parse_excel_sheet', { fileId, sheetName });
   }
 
   static async validateData(data: NetworkConfigRow[]): Promise<NetworkConfigRow[]> {
@@ -2,18 +2,25 @@ export interface NetworkConfigRow {
   blueprint?: string;
   server_label?: string;
   is_external?: boolean;
-  server_tags?: string;
+  server_tags?: string[];
   switch_tags?: string;
   link_group_ifname?: string;
   link_group_lag_mode?: string;
-  link_group_ct_names?: string;
+  link_group_ct_names?: string[];
   link_group_tags?: string;
   link_speed?: string;
   server_ifname?: string;
   switch_label?: string;
   switch_ifname?: string;
-  link_tags?: string;
+  link_tags?: string[];
   comment?: string;
+  source?: RowSource;
+}
+
+export interface RowSource {
+  sheet_name: string;
+  row: number; // 1-based sheet row
+  columns: Record<string, string>; // Field name -> source column letter (e.g. "F")
 }
 
 export interface ValidationError {